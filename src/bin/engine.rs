@@ -0,0 +1,125 @@
+use std::{
+    io::{self, BufRead},
+    time::Duration,
+};
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use pount::{
+    board::bundle::{BoardLimit, GameState},
+    bot::{
+        greedy::GreedyBot,
+        protocol::{EngineCommand, EngineResponse},
+        strategy::{Bot, BotView, SearchBudget},
+    },
+    card::{
+        bundle::{Card, CardColor},
+        deck::DeckDefinition,
+    },
+};
+
+/// Name the engine answers `pount` with
+const ENGINE_NAME: &str = "pount greedy";
+
+/// Reference engine speaking the engine protocol on stdin and stdout, playing like the greedy
+/// bot. A starting point for engines in other languages
+#[derive(Default)]
+struct Engine {
+    players: Vec<Vec<CardColor>>,
+    board_limit: BoardLimit,
+    /// The board and whose turn it is
+    position: Option<(Box<GameState>, usize)>,
+    card: Option<Card>,
+}
+
+impl Engine {
+    /// Handle a single command, returns the answers to print
+    fn handle(&mut self, command: EngineCommand, rng: &mut StdRng) -> Vec<EngineResponse> {
+        match command {
+            EngineCommand::Pount => vec![
+                EngineResponse::Id {
+                    name: ENGINE_NAME.to_string(),
+                },
+                EngineResponse::PountOk,
+            ],
+            EngineCommand::IsReady => vec![EngineResponse::ReadyOk],
+            EngineCommand::NewGame => {
+                *self = Engine::default();
+                vec![]
+            }
+            EngineCommand::Players(players) => {
+                self.players = players;
+                vec![]
+            }
+            EngineCommand::Limit(board_limit) => {
+                self.board_limit = board_limit;
+                vec![]
+            }
+            EngineCommand::Position {
+                board_state, turn, ..
+            } => {
+                if turn >= self.players.len() {
+                    self.position = None;
+                    return vec![EngineResponse::Info(format!(
+                        "error turn {} but there are {} players",
+                        turn,
+                        self.players.len()
+                    ))];
+                }
+                self.position = Some((board_state, turn));
+                vec![]
+            }
+            EngineCommand::Card(card) => {
+                self.card = Some(card);
+                vec![]
+            }
+            EngineCommand::Go { playouts, movetime } => self.go(playouts, movetime, rng),
+            EngineCommand::Quit => vec![],
+        }
+    }
+
+    fn go(&self, playouts: usize, movetime: u64, rng: &mut StdRng) -> Vec<EngineResponse> {
+        let (Some((board_state, turn)), Some(card)) = (&self.position, self.card) else {
+            return vec![
+                EngineResponse::Info("go needs a position and a card first".to_string()),
+                EngineResponse::BestMove(None),
+            ];
+        };
+
+        // The deck and the cards played so far are not part of the protocol, the greedy bot does
+        // not need them
+        let view = BotView {
+            board_state,
+            card,
+            board_limit: self.board_limit,
+            seat: *turn,
+            players: &self.players,
+            deck: &DeckDefinition::default(),
+            played: &[],
+            budget: SearchBudget {
+                playouts,
+                time_limit: Duration::from_millis(movetime),
+            },
+        };
+        vec![EngineResponse::BestMove(GreedyBot.choose_move(&view, rng))]
+    }
+}
+
+fn main() {
+    let mut engine = Engine::default();
+    let mut rng = StdRng::from_entropy();
+
+    for line in io::stdin().lock().lines().map_while(Result::ok) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let responses = match line.parse::<EngineCommand>() {
+            Ok(EngineCommand::Quit) => break,
+            Ok(command) => engine.handle(command, &mut rng),
+            Err(err) => vec![EngineResponse::Info(format!("error {}", err))],
+        };
+        for response in responses {
+            println!("{}", response);
+        }
+    }
+}
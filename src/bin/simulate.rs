@@ -0,0 +1,31 @@
+use bevy::{log::Level, prelude::*};
+use clap::Parser;
+
+use pount::{cli::Cli, headless::HeadlessPlugin, simulation::SimulationPlugin};
+
+/// Play bot-vs-bot games without a window and print the aggregate results. Every seat is played
+/// by a bot, `--bot` fills the seats in turn order and the remaining seats play random moves
+#[derive(Parser, Debug)]
+#[command(name = "simulate")]
+struct SimulateCli {
+    /// Number of games to play
+    #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u64).range(1..))]
+    games: u64,
+
+    #[command(flatten)]
+    game: Cli,
+}
+
+fn main() {
+    let cli = SimulateCli::parse();
+    let mut app = App::new();
+
+    app.add_plugins(HeadlessPlugin {
+        log_level: Level::WARN,
+    });
+    cli.game.insert_resources(&mut app);
+    app.add_plugins(SimulationPlugin {
+        games: cli.games as usize,
+    });
+    app.run();
+}
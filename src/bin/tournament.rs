@@ -0,0 +1,135 @@
+use std::path::PathBuf;
+
+use bevy::{log::Level, prelude::*};
+use clap::Parser;
+
+use pount::{
+    board::bundle::BOARD_SIZE,
+    bot::{
+        difficulty::Difficulty,
+        plugin::BotSeats,
+        strategy::{BotKind, BotSetup},
+    },
+    card::sequence::GameSeed,
+    cli::Cli,
+    headless::HeadlessPlugin,
+    tournament::{Sprt, Tournament, TournamentPlugin},
+};
+
+/// Play bot A against bot B over many seeds with swapped seats and report the Elo difference of
+/// A over B. With `--sprt` the tournament stops as soon as it is clear whether A is stronger
+#[derive(Parser, Debug)]
+#[command(name = "tournament")]
+struct TournamentCli {
+    /// Bot A, usually the one being tuned
+    #[arg(long, value_enum, default_value_t = BotKind::Mcts)]
+    a: BotKind,
+    #[arg(long, value_enum)]
+    a_difficulty: Option<Difficulty>,
+    /// Command starting an external engine to play as A instead
+    #[arg(long)]
+    a_engine: Option<String>,
+
+    /// Bot B, usually the reference
+    #[arg(long, value_enum, default_value_t = BotKind::Greedy)]
+    b: BotKind,
+    #[arg(long, value_enum)]
+    b_difficulty: Option<Difficulty>,
+    /// Command starting an external engine to play as B instead
+    #[arg(long)]
+    b_engine: Option<String>,
+
+    /// Most games to play, two per seed
+    #[arg(long, default_value_t = 1000)]
+    max_games: usize,
+
+    /// Stop early with a sequential probability ratio test
+    #[arg(long)]
+    sprt: bool,
+    /// Elo difference if A is not stronger
+    #[arg(long, default_value_t = 0.0)]
+    elo0: f64,
+    /// Elo difference if A is stronger
+    #[arg(long, default_value_t = 10.0)]
+    elo1: f64,
+    /// Chance to wrongly accept that A is stronger
+    #[arg(long, default_value_t = 0.05)]
+    alpha: f64,
+    /// Chance to wrongly reject that A is stronger
+    #[arg(long, default_value_t = 0.05)]
+    beta: f64,
+
+    /// Seed the first game is dealt from, random if not given. Every next seed is one higher
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Name of a deck in the decks directory, e.g. `jokers`
+    #[arg(long, conflicts_with = "deck")]
+    variant: Option<String>,
+
+    /// Path to a deck definition file
+    #[arg(long)]
+    deck: Option<PathBuf>,
+
+    /// All cards have to fit within a square of this many tiles
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..=BOARD_SIZE as u64))]
+    board_limit: Option<u64>,
+
+    /// Most playouts a searching bot runs per move
+    #[arg(long)]
+    playouts: Option<usize>,
+
+    /// Most milliseconds a searching bot thinks per move
+    #[arg(long)]
+    think_ms: Option<u64>,
+}
+
+fn main() {
+    let cli = TournamentCli::parse();
+
+    // Seats are swapped between two players, and a seed makes the tournament reproducible
+    let first_seed = cli.seed.unwrap_or_else(|| GameSeed::random().0);
+    let game = Cli {
+        players: Some(2),
+        seed: Some(first_seed),
+        variant: cli.variant.clone(),
+        deck: cli.deck.clone(),
+        board_limit: cli.board_limit,
+        playouts: cli.playouts,
+        think_ms: cli.think_ms,
+        ..default()
+    };
+    println!(
+        "Tournament A {} vs B {}, first seed {}",
+        cli.a, cli.b, first_seed
+    );
+
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin {
+        log_level: Level::WARN,
+    });
+    game.insert_resources(&mut app);
+
+    let setup = |kind, difficulty| Some(BotSetup { kind, difficulty });
+    let mut bot_seats = BotSeats::new(&[
+        setup(cli.a, cli.a_difficulty),
+        setup(cli.b, cli.b_difficulty),
+    ]);
+    bot_seats.seat_engines(0, cli.a_engine.as_slice());
+    bot_seats.seat_engines(1, cli.b_engine.as_slice());
+    app.insert_resource(bot_seats);
+
+    app.add_plugins(TournamentPlugin {
+        tournament: Tournament {
+            first_seed,
+            max_games: cli.max_games,
+            sprt: cli.sprt.then_some(Sprt {
+                elo0: cli.elo0,
+                elo1: cli.elo1,
+                alpha: cli.alpha,
+                beta: cli.beta,
+            }),
+        },
+    });
+    app.run();
+}
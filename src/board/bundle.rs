@@ -7,8 +7,18 @@ pub const BOARD_SIZE: usize = 10;
 
 type Board = [[Tile; BOARD_SIZE]; BOARD_SIZE];
 
+/// All cards have to fit within a square of this many tiles, [`BOARD_SIZE`] means no limit
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardLimit(pub usize);
+
+impl Default for BoardLimit {
+    fn default() -> Self {
+        BoardLimit(BOARD_SIZE)
+    }
+}
+
 // TODO remove the pub from Board, to make the GameBoard data structure opaque
-#[derive(Resource)]
+#[derive(Resource, Clone)]
 pub struct GameState {
     pub board: Board,
     // TODO it does not seem very clean to pass a bool everywhere just to see if this was the first
@@ -48,22 +58,40 @@ impl GameState {
     pub fn board(&self) -> &Board {
         &self.board
     }
-}
 
-impl std::fmt::Debug for GameState {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (y, tiles) in self.board().iter().enumerate() {
-            for (x, tile) in tiles.iter().enumerate() {
-                if let Some(top_card) = tile.cards.last() {
-                    write!(f, "({},{}) {:?} ", x, y, top_card).unwrap();
+    /// Number of cards on the board, skipped cards are not on it
+    pub fn num_cards(&self) -> usize {
+        self.board
+            .iter()
+            .flatten()
+            .map(|tile| tile.cards.len())
+            .sum()
+    }
+
+    /// Would all cards still fit within the [`BoardLimit`] with a card placed at (x,y)?
+    pub fn fits_within(&self, x: usize, y: usize, limit: BoardLimit) -> bool {
+        let (mut min_x, mut max_x, mut min_y, mut max_y) = (x, x, y, y);
+        for (tile_y, tiles) in self.board.iter().enumerate() {
+            for (tile_x, tile) in tiles.iter().enumerate() {
+                if !tile.cards.is_empty() {
+                    min_x = min_x.min(tile_x);
+                    max_x = max_x.max(tile_x);
+                    min_y = min_y.min(tile_y);
+                    max_y = max_y.max(tile_y);
                 }
             }
         }
-        Ok(())
+        max_x - min_x < limit.0 && max_y - min_y < limit.0
+    }
+}
+
+impl std::fmt::Debug for GameState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.board_notation())
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Tile {
     pub cards: Vec<Card>,
 }
@@ -1,19 +1,23 @@
 use bevy::prelude::*;
 
-use crate::debug::LogTimer;
+use crate::{
+    card::{deck::DeckDefinition, spawn::CardIndex},
+    debug::LogTimer,
+};
 
 use super::bundle::GameState;
 
-pub fn log_gamestate(time: Res<Time>, mut timer: ResMut<LogTimer>, board_state: Res<GameState>) {
+pub fn log_gamestate(
+    time: Res<Time>,
+    mut timer: ResMut<LogTimer>,
+    board_state: Res<GameState>,
+    card_index: Res<CardIndex>,
+    deck: Res<DeckDefinition>,
+) {
     if timer.0.tick(time.delta()).finished() {
-        // for (y, tiles) in board_state.board().iter().enumerate() {
-        //     for (x, tile) in tiles.iter().enumerate() {
-        //         if let Some(top_card) = tile.cards.last() {
-        //             let board_coordinates = BoardCoordinates::from_xyz(x, y, tile.cards.len());
-        //             info!("{:?} at position: {:?}", top_card, board_coordinates);
-        //         }
-        //     }
-        // }
-        info!("{:?}", *board_state);
+        info!(
+            "{}",
+            board_state.to_notation(card_index.index, card_index.index % deck.num_players())
+        );
     }
 }
@@ -1,21 +1,30 @@
 use bevy::prelude::*;
 
-use crate::schedule::InGameSet;
+use crate::{schedule::InGameSet, state::AppState};
 
 use super::{
-    bundle::setup_board,
-    win_condition::{check_wincondition, PlayerWinEntity},
+    bundle::{setup_board, BoardLimit},
+    restart::DealNumber,
+    win_condition::{check_wincondition, show_player_win, GameFinished, PlayerWinEntity},
 };
 
 pub struct BoardPlugin;
 
 impl Plugin for BoardPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<BoardLimit>();
         app.insert_resource(PlayerWinEntity(None));
+        app.init_resource::<DealNumber>();
+        app.add_event::<GameFinished>();
         app.add_systems(Startup, setup_board);
         app.add_systems(
             Update,
-            check_wincondition.in_set(InGameSet::CheckWincondition),
+            (
+                check_wincondition,
+                show_player_win.run_if(in_state(AppState::Replay)),
+            )
+                .chain()
+                .in_set(InGameSet::CheckWincondition),
         );
     }
 }
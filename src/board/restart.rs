@@ -1,52 +1,98 @@
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 
 use crate::{
+    bot::plugin::BotRng,
     card::{
-        bundle::CardMarker, sequence::CardSequence, show_next::CurrentInfoBox, spawn::CardIndex,
+        bundle::CardMarker,
+        deck::DeckDefinition,
+        sequence::{CardSequence, GameSeed},
+        spawn::CardIndex,
+        undo::CardHistory,
     },
-    keys::KeyMap,
+    daily::LeaveDailyContext,
+    hint::HintsUsed,
+    keys::keymap::{Action, ActionInput},
+    replay::record::ReplayRecorder,
+    state::AppState,
 };
 
 use super::{bundle::GameState, win_condition::PlayerWinEntity};
 
-#[allow(clippy::too_many_arguments)]
-pub fn restart_game(
+/// Counts the games dealt so far, tells a finished game apart from the same [`GameSeed`] dealt again
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DealNumber(pub u64);
+
+/// Group of all system parameters used to reset the board to a fresh game
+#[derive(SystemParam)]
+pub struct ResetContext<'w> {
+    pub board_state: ResMut<'w, GameState>,
+    pub card_sequence: ResMut<'w, CardSequence>,
+    pub game_seed: ResMut<'w, GameSeed>,
+    pub deck: Res<'w, DeckDefinition>,
+    pub card_index: ResMut<'w, CardIndex>,
+    pub card_history: ResMut<'w, CardHistory>,
+    pub replay_recorder: ResMut<'w, ReplayRecorder>,
+    pub bot_rng: ResMut<'w, BotRng>,
+    pub hints_used: ResMut<'w, HintsUsed>,
+    pub deal_number: ResMut<'w, DealNumber>,
+}
+
+impl ResetContext<'_> {
+    /// Start over with the given [`CardSequence`], dealt from [`GameSeed`]. The board entities are
+    /// despawned by [`despawn_board`] on the way
+    pub fn reset(&mut self, game_seed: GameSeed, card_sequence: CardSequence) {
+        // reset the board state, the cards it referenced are gone
+        *self.board_state = GameState::reset();
+        self.card_history.0 = None;
+
+        *self.game_seed = game_seed;
+        *self.bot_rng = BotRng::from_game_seed(game_seed);
+        *self.card_sequence = card_sequence;
+        self.card_index.index = 0;
+        self.hints_used.0 = 0;
+        self.deal_number.0 += 1;
+
+        // the next finished game goes into a new replay file
+        *self.replay_recorder = ReplayRecorder::default();
+    }
+
+    /// Clear the board and start over with a [`CardSequence`] dealt from the [`DeckDefinition`]
+    pub fn new_game(&mut self, game_seed: GameSeed) {
+        let card_sequence = CardSequence::generate_full_sequence(&self.deck, game_seed);
+        self.reset(game_seed, card_sequence);
+    }
+}
+
+/// System to despawn all cards and the player win notification, run whenever the board is about
+/// to be set up again
+pub fn despawn_board(
     mut commands: Commands,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
     query: Query<Entity, With<CardMarker>>,
-    mut board_state: ResMut<GameState>,
-    mut card_sequence: ResMut<CardSequence>,
     mut player_win_entity: ResMut<PlayerWinEntity>,
-    mut card_index: ResMut<CardIndex>,
-    mut current_card_entity: ResMut<CurrentInfoBox>,
-    keymap: Res<KeyMap>,
 ) {
-    if keyboard_input.just_pressed(
-        keymap
-            .0
-            .get("restart")
-            .cloned()
-            .expect("Restart keymap not found"),
-    ) {
-        // despawn player win notification
-        if let Some(entity) = player_win_entity.0 {
-            info!("despawning player win notification {:?}", entity);
-            commands.entity(entity).despawn_recursive();
-            player_win_entity.0 = None;
-        }
-
-        // despawn all current cards, and the show_next info card
-        for entity in query.iter() {
-            commands.entity(entity).despawn_recursive();
-        }
-        // show_next card entity has been despawned above, set resource to None
-        current_card_entity.0 = None;
-
-        // reset the board state
-        *board_state = GameState::reset();
-
-        // generate a new cardsequence
-        *card_sequence = CardSequence::generate_full_sequence();
-        card_index.index = 0;
+    // despawn player win notification
+    if let Some(entity) = player_win_entity.0.take() {
+        info!("despawning player win notification {:?}", entity);
+        commands.entity(entity).despawn_recursive();
+    }
+
+    // despawn all current cards
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// System to deal a new game with the same seats, stops watching a replay or playing the daily
+/// challenge
+pub fn restart_game(
+    mut commands: Commands,
+    input: ActionInput,
+    mut leave_daily: LeaveDailyContext,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if input.just_pressed(Action::Restart) {
+        info!("Restarting");
+        leave_daily.leave(&mut commands);
+        next_state.set(AppState::PlayerSetup);
     }
 }
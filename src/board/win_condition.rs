@@ -1,6 +1,9 @@
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
 
-const CARDS_TO_WIN: i32 = 4;
+pub const CARDS_TO_WIN: i32 = 4;
+/// One direction of every line a win can be made in: horizontal, vertical and both diagonals
+pub const LINE_DIRECTIONS: [(i32, i32); 4] = [(1, 0), (0, 1), (1, 1), (1, -1)];
 pub const NEIGHBOURS: [(i32, i32); 8] = [
     (-1, -1),
     (-1, 0),
@@ -11,19 +14,20 @@ pub const NEIGHBOURS: [(i32, i32); 8] = [
     (1, 0),
     (1, 1),
 ];
-const WIN_TEXT_FONT_SIZE: f32 = 100.0;
-const WIN_TEXT_BOX_Z: f32 = 100.0;
-const WIN_TEXT_BOX_X: f32 = WIN_TEXT_FONT_SIZE * 5.0;
-const WIN_TEXT_BOX_Y: f32 = WIN_TEXT_FONT_SIZE * 3.0;
+const WIN_TEXT_FONT_SIZE: f32 = 60.0;
+const WIN_TEXT_MARGIN: f32 = 20.0;
+const WIN_TEXT_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.7);
 
 use crate::{
     asset_loader::AssetStore,
     board::bundle::GameState,
-    camera::{CAMERA_OFFSET_X, CAMERA_OFFSET_Y},
     card::{
-        bundle::Card,
-        spawn::{CARD_TEXT_Z_OFFSET},
+        bundle::{Card, CardColor},
+        deck::DeckDefinition,
+        sequence::CardSequence,
+        spawn::CardIndex,
     },
+    profile::plugin::SeatProfiles,
 };
 
 use super::bundle::BOARD_SIZE;
@@ -31,126 +35,228 @@ use super::bundle::BOARD_SIZE;
 #[derive(Resource)]
 pub struct PlayerWinEntity(pub Option<Entity>);
 
-pub fn check_wincondition(
-    mut commands: Commands,
-    board_state: Res<GameState>,
-    asset_store: Res<AssetStore>,
-    windows: Query<&Window>,
-    mut player_win_entity: ResMut<PlayerWinEntity>,
-) {
+/// Outcome of a finished game
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum GameResult {
+    /// A player completed a line of [`CARDS_TO_WIN`] cards in this color
+    Win { color: CardColor },
+    /// All cards were placed without anyone completing a line
+    Draw,
+}
+
+/// Sent whenever the board reaches a finished state
+#[derive(Event, Debug)]
+pub struct GameFinished(pub GameResult);
+
+/// Group of all system parameters used to check the win condition
+#[derive(SystemParam)]
+pub struct WinConditionContext<'w> {
+    pub board_state: Res<'w, GameState>,
+    pub card_index: Res<'w, CardIndex>,
+    pub card_sequence: Res<'w, CardSequence>,
+    pub game_finished: EventWriter<'w, GameFinished>,
+}
+
+/// System to send [`GameFinished`] once the board is won or no cards are left, needs no window so
+/// it also runs headless
+pub fn check_wincondition(mut context: WinConditionContext) {
+    let board_state = &context.board_state;
     if board_state.is_changed() {
         info!("Board state changed, checking wincondition");
 
-        for (y, tiles) in board_state.board().iter().enumerate() {
-            for (x, tile) in tiles.iter().enumerate() {
-                if let Some(top_card) = tile.cards.last() {
-                    // info!("checking neighbours of: {:?}", top_card);
-                    if let Some(winning_card_streak) =
-                        check_card_neighbours(*top_card, x, y, &board_state)
-                    {
-                        let entity = on_player_win(
-                            winning_card_streak,
-                            &mut commands,
-                            &asset_store,
-                            windows.single(),
-                        );
-
-                        info!("Inserting player win entity: {:?}", entity);
-                        player_win_entity.0 = Some(entity);
-
-                        // early return or else a player will always win at least 2 times, once for
-                        // each end of the color streak.
-                        // this will cause multiple calls to on_player_win, which is not expected
-                        return;
+        // only the first line counts, or else a player will always win at least 2 times, once for
+        // each end of the color streak
+        if let Some(winning_card_streak) = winning_line(board_state) {
+            context.game_finished.send(GameFinished(GameResult::Win {
+                color: winning_card_streak[0].color,
+            }));
+            return;
+        }
+
+        // Nobody won and there are no cards left to place
+        if !board_state.empty && context.card_index.index >= context.card_sequence.cards.len() {
+            info!("No cards left, game ended in a draw");
+            context.game_finished.send(GameFinished(GameResult::Draw));
+        }
+    }
+}
+
+/// The first line of [`CARDS_TO_WIN`] top cards of the same color on the board, if any
+pub fn winning_line(board_state: &GameState) -> Option<Vec<Card>> {
+    winning_tiles(board_state).map(|tiles| {
+        tiles
+            .into_iter()
+            .filter_map(|(x, y)| board_state.get_tile(x, y).cards.last().copied())
+            .collect()
+    })
+}
+
+/// Tiles of the [`winning_line`], as (x,y)
+pub fn winning_tiles(board_state: &GameState) -> Option<Vec<(usize, usize)>> {
+    for (y, tiles) in board_state.board().iter().enumerate() {
+        for (x, tile) in tiles.iter().enumerate() {
+            if let Some(top_card) = tile.cards.last().filter(|card| !card.color.is_neutral()) {
+                if let Some(winning_tiles) = check_card_neighbours(*top_card, x, y, board_state) {
+                    return Some(winning_tiles);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Length of the line of top cards in this color through (x,y) in any direction, counting (x,y)
+/// itself as this color whatever is on it
+pub fn line_length(board_state: &GameState, x: usize, y: usize, color: CardColor) -> usize {
+    longest_line(board_state, x, y, color).0
+}
+
+/// Like [`line_length`], together with the direction out of [`LINE_DIRECTIONS`] the line runs in
+pub fn longest_line(
+    board_state: &GameState,
+    x: usize,
+    y: usize,
+    color: CardColor,
+) -> (usize, (i32, i32)) {
+    let is_color = |x: i32, y: i32| {
+        let board_size = BOARD_SIZE as i32;
+        x >= 0
+            && x < board_size
+            && y >= 0
+            && y < board_size
+            && board_state
+                .get_tile(x as usize, y as usize)
+                .cards
+                .last()
+                .is_some_and(|card| card.color == color)
+    };
+
+    LINE_DIRECTIONS
+        .iter()
+        .map(|(dx, dy)| {
+            let mut length = 1;
+            for direction in [1, -1] {
+                let (mut nx, mut ny) = (x as i32, y as i32);
+                loop {
+                    nx += dx * direction;
+                    ny += dy * direction;
+                    if !is_color(nx, ny) {
+                        break;
                     }
+                    length += 1;
                 }
             }
+            (length, (*dx, *dy))
+        })
+        .max_by_key(|(length, _)| *length)
+        .unwrap_or((1, LINE_DIRECTIONS[0]))
+}
+
+/// Name of the seat owning this color, its profile if it has one, e.g. `Red` or `alice`
+pub fn winner_name(
+    color: CardColor,
+    deck: &DeckDefinition,
+    seat_profiles: &SeatProfiles,
+) -> String {
+    deck.players()
+        .iter()
+        .position(|colors| colors.contains(&color))
+        .and_then(|seat| seat_profiles.name(seat))
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{:?}", color))
+}
+
+/// System to show who won at the top of the screen when a watched replay reaches its last move, the
+/// [`GameOver`](crate::state::AppState::GameOver) screen does this for a game being played
+pub fn show_player_win(
+    mut commands: Commands,
+    mut game_finished: EventReader<GameFinished>,
+    asset_store: Res<AssetStore>,
+    mut player_win_entity: ResMut<PlayerWinEntity>,
+    deck: Res<DeckDefinition>,
+    seat_profiles: Res<SeatProfiles>,
+) {
+    for GameFinished(result) in game_finished.read() {
+        if let GameResult::Win { color } = result {
+            let text = format!("{} wins!", winner_name(*color, &deck, &seat_profiles));
+            let entity = on_player_win(*color, text, &mut commands, &asset_store);
+
+            info!("Inserting player win entity: {:?}", entity);
+            player_win_entity.0 = Some(entity);
         }
     }
 }
 
 fn on_player_win(
-    winning_card_streak: Vec<Card>,
+    color: CardColor,
+    text: String,
     commands: &mut Commands,
     asset_store: &Res<AssetStore>,
-    window: &Window,
 ) -> Entity {
-    let transform = Transform::from_xyz(
-        window.width() / 2.0 + CAMERA_OFFSET_X,
-        window.height() / 2.0 + CAMERA_OFFSET_Y,
-        WIN_TEXT_BOX_Z,
-    );
-
-    let entity = commands
-        .spawn(SpriteBundle {
-            sprite: Sprite {
-                color: Color::WHITE,
-                custom_size: Some(Vec2::new(WIN_TEXT_BOX_X, WIN_TEXT_BOX_Y)),
-                ..Default::default()
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                top: Val::Px(WIN_TEXT_MARGIN),
+                justify_content: JustifyContent::Center,
+                ..default()
             },
-            transform,
-            ..Default::default()
+            ..default()
         })
         .with_children(|parent| {
-            parent.spawn(Text2dBundle {
-                text: Text::from_section(
-                    "game over!".to_string(),
-                    TextStyle {
-                        font_size: WIN_TEXT_FONT_SIZE,
-                        color: winning_card_streak.first().unwrap().color,
-                        font: asset_store.font.clone(),
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        padding: UiRect::all(Val::Px(WIN_TEXT_MARGIN)),
+                        ..default()
                     },
-                ),
-                transform: Transform::from_xyz(0.0, 0.0, CARD_TEXT_Z_OFFSET),
-                ..Default::default()
-            });
+                    background_color: WIN_TEXT_BACKGROUND_COLOR.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        text,
+                        TextStyle {
+                            font_size: WIN_TEXT_FONT_SIZE,
+                            color: color.to_color(),
+                            font: asset_store.font.clone(),
+                        },
+                    ));
+                });
         })
-        .id();
-    entity
+        .id()
 }
 
-// Checks each neighbouring card if there are CARDS_TO_WIN cards of the same color
+/// Checks in each direction from (x,y) if there are [`CARDS_TO_WIN`] top cards of the same color in
+/// a row, returns their tiles starting at (x,y)
 fn check_card_neighbours(
     top_card: Card,
     x: usize,
     y: usize,
     board_state: &GameState,
-) -> Option<Vec<Card>> {
+) -> Option<Vec<(usize, usize)>> {
+    let board_size = BOARD_SIZE as i32;
     for (dx, dy) in NEIGHBOURS.iter() {
-        'check_color_streak: for card_number in 1i32..=CARDS_TO_WIN {
-            let mut current_color_streak = vec![top_card];
-
-            let dx = dx * card_number;
-            let dy = dy * card_number;
-            if let Some(nx) = (x as i32).checked_add(dx) {
-                if let Some(ny) = (y as i32).checked_add(dy) {
-                    let board_size: i32 = BOARD_SIZE
-                        .try_into()
-                        .expect("BOARD_SIZE should always be a positive integer");
-                    if nx >= 0 && nx < board_size && ny >= 0 && ny < board_size {
-                        let nx = nx as usize;
-                        let ny = ny as usize;
-
-                        if let Some(neighbouring_card) = board_state.get_tile(nx, ny).cards.last() {
-                            if neighbouring_card.color != top_card.color {
-                                break 'check_color_streak;
-                            } else {
-                                current_color_streak.push(*neighbouring_card);
-                                // info!("color streak {:?}", current_color_streak);
-                            }
-                            if card_number == CARDS_TO_WIN - 1 {
-                                return Some(current_color_streak);
-                            }
-                        } else {
-                            break 'check_color_streak;
-                        }
-                    }
+        let mut color_streak = vec![(x, y)];
+        for card_number in 1..CARDS_TO_WIN {
+            let nx = x as i32 + dx * card_number;
+            let ny = y as i32 + dy * card_number;
+            if nx < 0 || nx >= board_size || ny < 0 || ny >= board_size {
+                break;
+            }
+
+            let (nx, ny) = (nx as usize, ny as usize);
+            match board_state.get_tile(nx, ny).cards.last() {
+                Some(neighbouring_card) if neighbouring_card.color == top_card.color => {
+                    color_streak.push((nx, ny))
                 }
+                _ => break,
             }
         }
+        if color_streak.len() == CARDS_TO_WIN as usize {
+            return Some(color_streak);
+        }
     }
-
     None
-    // check top_card neighbours, if same color
-    // continue checking in that direction, if 4 consequitive WIN
 }
@@ -0,0 +1,148 @@
+use std::{fmt, time::Duration};
+
+use clap::ValueEnum;
+use rand::{distributions::WeightedIndex, prelude::Distribution, rngs::StdRng, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    board::win_condition::{line_length, CARDS_TO_WIN},
+    coordinates::BoardCoordinates,
+};
+
+use super::strategy::{best_move, Bot, BotView, SearchBudget};
+
+/// How strong a bot plays, lower difficulties think shorter, make random mistakes and sometimes
+/// overlook that an opponent is about to win
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Hard,
+        Difficulty::Expert,
+    ];
+
+    /// How long a searching bot may think at this difficulty. The search runs off the main thread,
+    /// see [`play_bot_move`](super::plugin::play_bot_move), but players still wait for it, so even
+    /// [`Difficulty::Expert`] thinks no longer than a second
+    pub fn budget(self) -> SearchBudget {
+        let (playouts, millis) = match self {
+            Difficulty::Easy => (50, 100),
+            Difficulty::Medium => (200, 250),
+            Difficulty::Hard => (1000, 500),
+            Difficulty::Expert => (4000, 1000),
+        };
+        SearchBudget {
+            playouts,
+            time_limit: Duration::from_millis(millis),
+        }
+    }
+
+    /// Softmax temperature over the scores of all moves, scaled to between 0 and 1. Higher picks
+    /// worse moves more often, 0 always picks the best one
+    pub fn temperature(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.25,
+            Difficulty::Medium => 0.1,
+            Difficulty::Hard => 0.03,
+            Difficulty::Expert => 0.0,
+        }
+    }
+
+    /// Chance to overlook every tile an opponent could win on during a move
+    pub fn threat_blindness(self) -> f64 {
+        match self {
+            Difficulty::Easy => 0.5,
+            Difficulty::Medium => 0.2,
+            Difficulty::Hard => 0.05,
+            Difficulty::Expert => 0.0,
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Plays any [`Bot`] at a [`Difficulty`], by limiting its budget and adding mistakes on top of
+/// its scores
+pub struct HandicappedBot {
+    pub bot: Box<dyn Bot>,
+    pub difficulty: Difficulty,
+}
+
+impl Bot for HandicappedBot {
+    fn score_moves(&mut self, view: &BotView, rng: &mut StdRng) -> Vec<(BoardCoordinates, f32)> {
+        let view = BotView {
+            budget: self.difficulty.budget(),
+            ..*view
+        };
+        let scored = self.bot.score_moves(&view, rng);
+        if !rng.gen_bool(self.difficulty.threat_blindness()) {
+            return scored;
+        }
+
+        // Act as if the blocking tiles were never seen, unless nothing else is left
+        let unaware: Vec<_> = scored
+            .iter()
+            .filter(|(coordinates, _)| !blocks_win(&view, coordinates))
+            .cloned()
+            .collect();
+        if unaware.is_empty() {
+            scored
+        } else {
+            unaware
+        }
+    }
+
+    fn choose_move(&mut self, view: &BotView, rng: &mut StdRng) -> Option<BoardCoordinates> {
+        let scored = self.score_moves(view, rng);
+        softmax_move(scored, self.difficulty.temperature(), rng)
+    }
+}
+
+/// Does placing the card here only take a tile an opponent could win on?
+fn blocks_win(view: &BotView, coordinates: &BoardCoordinates) -> bool {
+    let (x, y, _) = coordinates.as_xys();
+    let cards_to_win = CARDS_TO_WIN as usize;
+    let wins = !view.card.color.is_neutral()
+        && line_length(view.board_state, x, y, view.card.color) >= cards_to_win;
+    !wins
+        && view
+            .opponent_colors()
+            .any(|color| line_length(view.board_state, x, y, color) >= cards_to_win)
+}
+
+/// Pick a move with a chance that grows with its score. Scores are first scaled to between 0 and
+/// 1, so the temperature means the same for every [`Bot`]
+fn softmax_move(
+    scored: Vec<(BoardCoordinates, f32)>,
+    temperature: f32,
+    rng: &mut StdRng,
+) -> Option<BoardCoordinates> {
+    let (min, max) = scored.iter().fold(
+        (f32::INFINITY, f32::NEG_INFINITY),
+        |(min, max), (_, score)| (min.min(*score), max.max(*score)),
+    );
+    if temperature <= 0.0 || max <= min {
+        return best_move(scored, rng);
+    }
+
+    let weights = scored
+        .iter()
+        .map(|(_, score)| ((score - max) / (max - min) / temperature).exp());
+    let index = WeightedIndex::new(weights).ok()?.sample(rng);
+    scored
+        .into_iter()
+        .nth(index)
+        .map(|(coordinates, _)| coordinates)
+}
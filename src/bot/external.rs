@@ -0,0 +1,203 @@
+use std::{
+    error::Error,
+    io::{BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use bevy::prelude::*;
+use rand::{rngs::StdRng, seq::SliceRandom};
+
+use crate::coordinates::BoardCoordinates;
+
+use super::{
+    protocol::{EngineCommand, EngineResponse},
+    strategy::{Bot, BotView},
+};
+
+/// How long an engine may take to start and answer `pount`
+const ENGINE_STARTUP: Duration = Duration::from_secs(5);
+/// How long an engine may take to answer `go` on top of its search budget
+const ENGINE_GRACE: Duration = Duration::from_secs(2);
+/// How long an engine may take to exit after `quit` before it is killed
+const ENGINE_SHUTDOWN: Duration = Duration::from_secs(1);
+
+/// A [`Bot`] played by an external program speaking the [engine protocol](super::protocol). If
+/// the engine fails or answers an illegal tile, a random legal tile is played instead so the game
+/// can go on
+pub struct ExternalBot {
+    /// Name the engine gave itself, or its program name
+    pub name: String,
+    child: Child,
+    stdin: ChildStdin,
+    /// Lines the engine printed, read on a separate thread so a silent engine times out
+    lines: Mutex<Receiver<String>>,
+    /// Number of cards placed when the engine last moved, to notice a new game started
+    last_turn: Option<usize>,
+}
+
+impl ExternalBot {
+    /// Start the engine with a command line such as `./engine --verbose` and wait for its
+    /// `pountok`
+    pub fn spawn(command: &str) -> Result<Self, Box<dyn Error>> {
+        let mut words = command.split_whitespace();
+        let program = words.next().ok_or("empty engine command")?;
+        let mut child = Command::new(program)
+            .args(words)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().ok_or("engine has no stdin")?;
+        let stdout = child.stdout.take().ok_or("engine has no stdout")?;
+
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut bot = Self {
+            name: program.to_string(),
+            child,
+            stdin,
+            lines: Mutex::new(receiver),
+            last_turn: None,
+        };
+        bot.send(&EngineCommand::Pount)?;
+        let deadline = Instant::now() + ENGINE_STARTUP;
+        loop {
+            match bot.receive(deadline)? {
+                EngineResponse::Id { name } => bot.name = name,
+                EngineResponse::PountOk => break,
+                _ => {}
+            }
+        }
+
+        info!("Started engine {}", bot.name);
+        Ok(bot)
+    }
+
+    fn send(&mut self, command: &EngineCommand) -> Result<(), Box<dyn Error>> {
+        debug!("{} < {}", self.name, command);
+        writeln!(self.stdin, "{}", command)?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    /// Wait for the next answer, lines that are not part of the protocol are skipped
+    fn receive(&mut self, deadline: Instant) -> Result<EngineResponse, Box<dyn Error>> {
+        let lines = self
+            .lines
+            .get_mut()
+            .map_err(|_| "engine output reader panicked")?;
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            let line = lines.recv_timeout(timeout).map_err(|err| match err {
+                RecvTimeoutError::Timeout => "engine did not answer in time",
+                RecvTimeoutError::Disconnected => "engine exited",
+            })?;
+            debug!("{} > {}", self.name, line);
+
+            match line.parse() {
+                Ok(response) => return Ok(response),
+                Err(err) => debug!("Ignoring engine output '{}': {}", line, err),
+            }
+        }
+    }
+
+    /// Tell the engine everything in the [`BotView`] and ask for its tile
+    fn best_move(&mut self, view: &BotView) -> Result<Option<BoardCoordinates>, Box<dyn Error>> {
+        let turn = view.played.len();
+        if self.last_turn.is_none_or(|last_turn| turn <= last_turn) {
+            self.send(&EngineCommand::NewGame)?;
+        }
+        self.last_turn = Some(turn);
+
+        self.send(&EngineCommand::Players(view.players.to_vec()))?;
+        self.send(&EngineCommand::Limit(view.board_limit))?;
+        self.send(&EngineCommand::Position {
+            board_state: Box::new(view.board_state.clone()),
+            next_card: turn,
+            turn: view.seat,
+        })?;
+        self.send(&EngineCommand::Card(view.card))?;
+        self.send(&EngineCommand::Go {
+            playouts: view.budget.playouts,
+            movetime: view.budget.time_limit.as_millis() as u64,
+        })?;
+
+        let deadline = Instant::now() + view.budget.time_limit + ENGINE_GRACE;
+        loop {
+            if let EngineResponse::BestMove(tile) = self.receive(deadline)? {
+                return Ok(tile);
+            }
+        }
+    }
+}
+
+impl Bot for ExternalBot {
+    /// Engines only name their favourite tile, every other legal tile scores 0
+    fn score_moves(&mut self, view: &BotView, rng: &mut StdRng) -> Vec<(BoardCoordinates, f32)> {
+        let best = self.choose_move(view, rng).map(|tile| tile.as_xys());
+        view.legal_moves()
+            .into_iter()
+            .map(|coordinates| {
+                let score = if Some(coordinates.as_xys()) == best {
+                    1.0
+                } else {
+                    0.0
+                };
+                (coordinates, score)
+            })
+            .collect()
+    }
+
+    fn choose_move(&mut self, view: &BotView, rng: &mut StdRng) -> Option<BoardCoordinates> {
+        let legal_moves = view.legal_moves();
+        let is_legal = |tile: &BoardCoordinates| {
+            legal_moves
+                .iter()
+                .any(|legal| legal.as_xys() == tile.as_xys())
+        };
+
+        match self.best_move(view) {
+            Ok(Some(tile)) if is_legal(&tile) => return Some(tile),
+            Ok(None) if legal_moves.is_empty() => return None,
+            Ok(Some(tile)) => warn!(
+                "Engine {} chose illegal tile {} for {} within {:?}, playing a random tile",
+                self.name, tile, view.card, view.board_limit
+            ),
+            Ok(None) => warn!(
+                "Engine {} found no tile for {} within {:?}, playing a random tile",
+                self.name, view.card, view.board_limit
+            ),
+            Err(err) => error!("Engine {} failed: {}", self.name, err),
+        }
+        legal_moves.choose(rng).cloned()
+    }
+}
+
+impl Drop for ExternalBot {
+    /// Ask the engine to quit, and kill it if it does not
+    fn drop(&mut self) {
+        let _ = self.send(&EngineCommand::Quit);
+
+        let deadline = Instant::now() + ENGINE_SHUTDOWN;
+        while Instant::now() < deadline {
+            if let Ok(Some(_)) = self.child.try_wait() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,56 @@
+use rand::rngs::StdRng;
+
+use crate::{
+    board::win_condition::{line_length, CARDS_TO_WIN},
+    coordinates::BoardCoordinates,
+};
+
+use super::strategy::{Bot, BotView};
+
+/// Score of a move that wins the game on the spot
+const WIN_SCORE: usize = 100;
+/// Score of a move that takes a tile an opponent could win on
+const BLOCK_WIN_SCORE: usize = 50;
+
+/// Looks one move ahead: wins if it can, blocks a winning tile of an opponent if it must, and
+/// otherwise extends its own longest line while covering the longest line of an opponent
+pub struct GreedyBot;
+
+impl GreedyBot {
+    /// How good is placing the card at these [`BoardCoordinates`], higher is better
+    pub fn score(view: &BotView, coordinates: &BoardCoordinates) -> usize {
+        let (x, y, _) = coordinates.as_xys();
+        let cards_to_win = CARDS_TO_WIN as usize;
+
+        let own = if view.card.color.is_neutral() {
+            0
+        } else {
+            line_length(view.board_state, x, y, view.card.color)
+        };
+        let block = view
+            .opponent_colors()
+            .map(|color| line_length(view.board_state, x, y, color))
+            .max()
+            .unwrap_or(0);
+
+        if own >= cards_to_win {
+            WIN_SCORE
+        } else if block >= cards_to_win {
+            BLOCK_WIN_SCORE
+        } else {
+            2 * own + block
+        }
+    }
+}
+
+impl Bot for GreedyBot {
+    fn score_moves(&mut self, view: &BotView, _rng: &mut StdRng) -> Vec<(BoardCoordinates, f32)> {
+        view.legal_moves()
+            .into_iter()
+            .map(|coordinates| {
+                let score = GreedyBot::score(view, &coordinates) as f32;
+                (coordinates, score)
+            })
+            .collect()
+    }
+}
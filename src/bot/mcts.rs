@@ -0,0 +1,303 @@
+use std::time::Instant;
+
+use rand::{rngs::StdRng, seq::SliceRandom};
+
+use crate::{
+    board::{
+        bundle::{BoardLimit, GameState},
+        win_condition::{line_length, CARDS_TO_WIN},
+    },
+    card::{bundle::Card, sequence::CardSequence, spawn::legal_moves},
+    coordinates::BoardCoordinates,
+};
+
+use super::strategy::{Bot, BotView};
+
+/// Exploration constant of the UCB1 formula, higher tries more different tiles
+const EXPLORATION: f32 = 0.7;
+
+/// Position of a tile on the board
+type TilePosition = (usize, usize);
+
+/// Monte Carlo Tree Search bot. It can not know in which order the remaining cards will be drawn,
+/// so every playout searches a different determinization: the cards nobody has seen yet are
+/// shuffled into a possible order. Tiles are then picked by how well they did across all of them
+pub struct MctsBot;
+
+impl Bot for MctsBot {
+    /// Scores are the share of playouts that started on each tile
+    fn score_moves(&mut self, view: &BotView, rng: &mut StdRng) -> Vec<(BoardCoordinates, f32)> {
+        let root_moves = view.legal_moves();
+        if root_moves.len() <= 1 {
+            return root_moves
+                .into_iter()
+                .map(|coordinates| (coordinates, 1.0))
+                .collect();
+        }
+
+        let mut tree = SearchTree::default();
+        let start = Instant::now();
+        for _ in 0..view.budget.playouts {
+            if start.elapsed() >= view.budget.time_limit {
+                break;
+            }
+            tree.playout(SampledGame::sample(view, rng), rng);
+        }
+
+        let playouts = tree.playouts().max(1) as f32;
+        root_moves
+            .into_iter()
+            .map(|coordinates| {
+                let (x, y, _) = coordinates.as_xys();
+                let visits = tree.visits((x, y)) as f32;
+                (coordinates, visits / playouts)
+            })
+            .collect()
+    }
+}
+
+/// A possible future of the current game: the real board, with the unseen cards in a sampled order
+struct SampledGame {
+    board_state: GameState,
+    board_limit: BoardLimit,
+    /// The card to place now, followed by the sampled cards
+    cards: Vec<Card>,
+    /// Index into `cards` of the next card to place
+    next: usize,
+    /// Turn of the first card in `cards`, to know whose card is whose
+    first_turn: usize,
+    num_players: usize,
+    winner: Option<usize>,
+}
+
+impl SampledGame {
+    /// Deal the cards each player has not placed yet, in a random order. Only uses what everyone
+    /// at the table knows: the deck composition and the cards placed so far
+    fn sample(view: &BotView, rng: &mut StdRng) -> Self {
+        let num_players = view.players.len();
+        let mut hands: Vec<Vec<Card>> = view
+            .players
+            .iter()
+            .map(|colors| CardSequence::generate_player_sequence(colors, view.deck, rng).cards)
+            .collect();
+
+        let turn = view.played.len();
+        let placed = view
+            .played
+            .iter()
+            .enumerate()
+            .chain(std::iter::once((turn, &view.card)));
+        for (card_turn, card) in placed {
+            let hand = &mut hands[card_turn % num_players];
+            if let Some(position) = hand.iter().position(|other| other == card) {
+                hand.swap_remove(position);
+            }
+        }
+        for hand in hands.iter_mut() {
+            hand.shuffle(rng);
+        }
+
+        let mut cards = vec![view.card];
+        let mut next_turn = turn + 1;
+        while let Some(card) = hands[next_turn % num_players].pop() {
+            cards.push(card);
+            next_turn += 1;
+        }
+
+        Self {
+            board_state: view.board_state.clone(),
+            board_limit: view.board_limit,
+            cards,
+            next: 0,
+            first_turn: turn,
+            num_players,
+            winner: None,
+        }
+    }
+
+    /// Seat that places the next card
+    fn seat(&self) -> usize {
+        (self.first_turn + self.next) % self.num_players
+    }
+
+    fn finished(&self) -> bool {
+        self.winner.is_some() || self.next >= self.cards.len()
+    }
+
+    fn legal_moves(&self) -> Vec<TilePosition> {
+        legal_moves(&self.cards[self.next], &self.board_state, self.board_limit)
+            .iter()
+            .map(|coordinates| {
+                let (x, y, _) = coordinates.as_xys();
+                (x, y)
+            })
+            .collect()
+    }
+
+    /// Would placing the next card here complete a line? A new line can only run through the tile
+    /// the card is placed on
+    fn wins(&self, (x, y): TilePosition) -> bool {
+        let card = self.cards[self.next];
+        !card.color.is_neutral()
+            && line_length(&self.board_state, x, y, card.color) >= CARDS_TO_WIN as usize
+    }
+
+    fn play(&mut self, tile: TilePosition) {
+        if self.wins(tile) {
+            self.winner = Some(self.seat());
+        }
+        let (x, y) = tile;
+        self.board_state
+            .get_tile_mut(x, y)
+            .cards
+            .push(self.cards[self.next]);
+        self.next += 1;
+    }
+
+    /// The next card fits nowhere and is skipped
+    fn skip(&mut self) {
+        self.next += 1;
+    }
+
+    /// Play random moves until the game is finished, a winning move is always taken
+    fn play_out(&mut self, rng: &mut StdRng) {
+        while !self.finished() {
+            let moves = self.legal_moves();
+            let winning_move = moves.iter().find(|tile| self.wins(**tile));
+            match winning_move.or_else(|| moves.choose(rng)) {
+                Some(tile) => self.play(*tile),
+                None => self.skip(),
+            }
+        }
+    }
+
+    /// Reward of the finished game for a seat, a draw is shared by everyone
+    fn reward(&self, seat: usize) -> f32 {
+        match self.winner {
+            Some(winner) if winner == seat => 1.0,
+            Some(_) => 0.0,
+            None => 1.0 / self.num_players as f32,
+        }
+    }
+}
+
+/// A tile placed during the search
+struct Node {
+    tile: TilePosition,
+    /// Seat that placed the card on `tile`
+    seat: usize,
+    visits: u32,
+    /// Sum of the rewards of `seat` over all playouts through this node
+    reward: f32,
+    /// Number of playouts this node could have been selected in, differs per determinization
+    available: u32,
+    children: Vec<usize>,
+}
+
+impl Node {
+    fn ucb(&self) -> f32 {
+        let visits = self.visits as f32;
+        self.reward / visits + EXPLORATION * ((self.available as f32).ln() / visits).sqrt()
+    }
+}
+
+/// Search tree shared by all determinizations, nodes are stored in one [`Vec`] and refer to each
+/// other by index
+#[derive(Default)]
+struct SearchTree {
+    nodes: Vec<Node>,
+    root_children: Vec<usize>,
+}
+
+impl SearchTree {
+    /// Select a path of known tiles, expand one new tile, finish the game randomly and update
+    /// every node on the path with the result
+    fn playout(&mut self, mut game: SampledGame, rng: &mut StdRng) {
+        let mut path: Vec<usize> = Vec::new();
+
+        while !game.finished() {
+            let moves = game.legal_moves();
+            if moves.is_empty() {
+                break;
+            }
+
+            let children = match path.last() {
+                Some(node) => self.nodes[*node].children.clone(),
+                None => self.root_children.clone(),
+            };
+            let available: Vec<usize> = children
+                .into_iter()
+                .filter(|child| moves.contains(&self.nodes[*child].tile))
+                .collect();
+            for child in available.iter() {
+                self.nodes[*child].available += 1;
+            }
+
+            let untried: Vec<TilePosition> = moves
+                .into_iter()
+                .filter(|tile| {
+                    !available
+                        .iter()
+                        .any(|child| self.nodes[*child].tile == *tile)
+                })
+                .collect();
+            if let Some(tile) = untried.choose(rng) {
+                let child = self.add_node(path.last().copied(), *tile, game.seat());
+                game.play(*tile);
+                path.push(child);
+                break;
+            }
+
+            let Some(child) = available
+                .into_iter()
+                .max_by(|a, b| self.nodes[*a].ucb().total_cmp(&self.nodes[*b].ucb()))
+            else {
+                break;
+            };
+            game.play(self.nodes[child].tile);
+            path.push(child);
+        }
+
+        game.play_out(rng);
+
+        for node in path {
+            let node = &mut self.nodes[node];
+            node.visits += 1;
+            node.reward += game.reward(node.seat);
+        }
+    }
+
+    fn add_node(&mut self, parent: Option<usize>, tile: TilePosition, seat: usize) -> usize {
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            tile,
+            seat,
+            visits: 0,
+            reward: 0.0,
+            available: 1,
+            children: Vec::new(),
+        });
+        match parent {
+            Some(parent) => self.nodes[parent].children.push(index),
+            None => self.root_children.push(index),
+        }
+        index
+    }
+
+    /// Number of playouts that started on this tile, the most visited tile is the most robust
+    /// choice
+    fn visits(&self, tile: TilePosition) -> u32 {
+        self.root_children
+            .iter()
+            .map(|child| &self.nodes[*child])
+            .find(|node| node.tile == tile)
+            .map_or(0, |node| node.visits)
+    }
+
+    fn playouts(&self) -> u32 {
+        self.root_children
+            .iter()
+            .map(|child| self.nodes[*child].visits)
+            .sum()
+    }
+}
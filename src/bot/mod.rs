@@ -0,0 +1,8 @@
+pub mod difficulty;
+pub mod external;
+pub mod greedy;
+pub mod mcts;
+pub mod plugin;
+pub mod protocol;
+pub mod random;
+pub mod strategy;
@@ -0,0 +1,322 @@
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::*,
+    tasks::{block_on, poll_once, AsyncComputeTaskPool, Task},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    board::win_condition::winning_line,
+    card::{
+        deck::DeckDefinition,
+        sequence::GameSeed,
+        spawn::{get_next_card, PlaceCardContext},
+    },
+    cli::Cli,
+    coordinates::BoardCoordinates,
+    schedule::InGameSet,
+    state::AppState,
+};
+
+use super::{
+    external::ExternalBot,
+    strategy::{choose_legal_move, Bot, BotKind, BotPosition, BotSetup, BotView, SearchBudget},
+};
+
+/// Seconds a bot waits before placing its card, so players can follow along
+const BOT_MOVE_SECONDS: f32 = 0.6;
+
+/// A seat played by a [`Bot`]. The bot is shared with the task searching its move, see
+/// [`play_bot_move`]
+pub struct BotSeat {
+    pub setup: BotSetup,
+    /// Shown for the seat, the name an [`ExternalBot`] gave itself or else the [`BotKind`]
+    pub name: String,
+    pub bot: Arc<Mutex<Box<dyn Bot>>>,
+}
+
+impl BotSeat {
+    pub fn new(setup: BotSetup, bot: Box<dyn Bot>) -> Self {
+        Self {
+            setup,
+            name: setup.kind.to_string(),
+            bot: Arc::new(Mutex::new(bot)),
+        }
+    }
+
+    pub fn engine(bot: ExternalBot) -> Self {
+        Self {
+            name: bot.name.clone(),
+            ..Self::new(BotSetup::new(BotKind::Engine), Box::new(bot))
+        }
+    }
+
+    /// Lock the bot, a bot whose search panicked is used as it was left
+    pub fn bot(&self) -> MutexGuard<'_, Box<dyn Bot>> {
+        self.bot.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Which seats are played by a [`Bot`], the others are played by people
+#[derive(Resource, Default)]
+pub struct BotSeats(pub Vec<Option<BotSeat>>);
+
+impl BotSeats {
+    pub fn new(setups: &[Option<BotSetup>]) -> Self {
+        BotSeats(
+            setups
+                .iter()
+                .map(|setup| setup.and_then(|setup| Some(BotSeat::new(setup, setup.build()?))))
+                .collect(),
+        )
+    }
+
+    /// A person in the first seat and the given bots in the seats after it, remaining seats are
+    /// played by people
+    pub fn opponents(num_players: usize, bots: &[BotSetup]) -> Self {
+        let setups: Vec<_> = std::iter::once(None)
+            .chain(bots.iter().copied().map(Some))
+            .chain(std::iter::repeat(None))
+            .take(num_players)
+            .collect();
+        BotSeats::new(&setups)
+    }
+
+    /// Bots in every seat, taken from `bots` in order and [`BotKind::Random`] for the rest
+    pub fn all(num_players: usize, bots: &[BotSetup]) -> Self {
+        let setups: Vec<_> = bots
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(BotSetup::new(BotKind::Random)))
+            .take(num_players)
+            .map(Some)
+            .collect();
+        BotSeats::new(&setups)
+    }
+
+    /// Start an [`ExternalBot`] for every command and seat them in turn order from `first_seat` on,
+    /// replacing whoever sat there. Engines that fail to start leave their seat as it was. Waits
+    /// for every engine, the game starts them with [`StartingEngines`] instead
+    pub fn seat_engines(&mut self, first_seat: usize, commands: &[String]) {
+        for (seat, command) in (first_seat..self.0.len()).zip(commands) {
+            match ExternalBot::spawn(command) {
+                Ok(bot) => self.seat_engine(seat, bot),
+                Err(err) => error!("Failed to start engine '{}': {}", command, err),
+            }
+        }
+    }
+
+    /// Seat a started engine, replacing whoever sat there
+    pub fn seat_engine(&mut self, seat: usize, bot: ExternalBot) {
+        match self.0.get_mut(seat) {
+            Some(bot_seat) => *bot_seat = Some(BotSeat::engine(bot)),
+            None => warn!("No seat {} for engine {}", seat + 1, bot.name),
+        }
+    }
+
+    /// Seat whose turn it is to place the card at this index of the
+    /// [`CardSequence`](crate::card::sequence::CardSequence)
+    pub fn seat_for_turn(&self, card_index: usize) -> usize {
+        card_index % self.0.len().max(1)
+    }
+
+    pub fn is_bot_turn(&self, card_index: usize) -> bool {
+        self.0
+            .get(self.seat_for_turn(card_index))
+            .is_some_and(Option::is_some)
+    }
+
+    /// Is any seat played by a person?
+    pub fn has_person(&self) -> bool {
+        self.0.iter().any(Option::is_none)
+    }
+
+    /// The [`BotSeat`] whose turn it is, [`None`] if a person plays it
+    pub fn bot_for_turn(&self, card_index: usize) -> Option<&BotSeat> {
+        let seat = self.seat_for_turn(card_index);
+        self.0.get(seat).and_then(Option::as_ref)
+    }
+
+    pub fn setups(&self) -> Vec<Option<BotSetup>> {
+        self.0
+            .iter()
+            .map(|seat| seat.as_ref().map(|seat| seat.setup))
+            .collect()
+    }
+}
+
+/// Random number generator every [`Bot`] draws from, seeded from the [`GameSeed`] so a game with
+/// the same seed and the same moves plays out the same
+#[derive(Resource)]
+pub struct BotRng(pub StdRng);
+
+impl BotRng {
+    pub fn from_game_seed(game_seed: GameSeed) -> Self {
+        BotRng(StdRng::seed_from_u64(game_seed.0))
+    }
+}
+
+pub struct BotPlugin;
+
+impl Plugin for BotPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BotSeats>();
+        app.init_resource::<SearchBudget>();
+        app.add_systems(Startup, start_engines);
+        app.add_systems(
+            Update,
+            (
+                seat_started_engines.run_if(resource_exists::<StartingEngines>),
+                play_bot_move.run_if(in_state(AppState::InGame)),
+            )
+                .chain()
+                .in_set(InGameSet::MutateBoard),
+        );
+    }
+}
+
+/// Engines given on the command line that are still starting, with the seat each one takes
+#[derive(Resource, Default)]
+pub struct StartingEngines(pub Vec<(usize, Task<Result<ExternalBot, String>>)>);
+
+/// System to start the engines given on the command line on the [`AsyncComputeTaskPool`], they
+/// take the seats after the `--bot`s once they answer
+fn start_engines(mut commands: Commands, cli: Res<Cli>) {
+    if cli.engines.is_empty() {
+        return;
+    }
+    let tasks = (1 + cli.bots.len()..)
+        .zip(cli.engines.iter().cloned())
+        .map(|(seat, command)| {
+            let task = AsyncComputeTaskPool::get().spawn(async move {
+                ExternalBot::spawn(&command)
+                    .map_err(|err| format!("Failed to start engine '{}': {}", command, err))
+            });
+            (seat, task)
+        })
+        .collect();
+    commands.insert_resource(StartingEngines(tasks));
+}
+
+/// System to seat the engines that finished starting
+fn seat_started_engines(
+    mut commands: Commands,
+    mut starting: ResMut<StartingEngines>,
+    mut bot_seats: ResMut<BotSeats>,
+) {
+    starting.0.retain_mut(|(seat, task)| {
+        let Some(started) = block_on(poll_once(task)) else {
+            return true;
+        };
+        match started {
+            Ok(bot) => bot_seats.seat_engine(*seat, bot),
+            Err(err) => error!("{}", err),
+        }
+        false
+    });
+    if starting.0.is_empty() {
+        commands.remove_resource::<StartingEngines>();
+    }
+}
+
+/// Group of all system parameters bots need to pick a move
+#[derive(SystemParam)]
+pub struct BotContext<'w> {
+    pub seats: Res<'w, BotSeats>,
+    pub rng: ResMut<'w, BotRng>,
+    pub deck: Res<'w, DeckDefinition>,
+    pub budget: Res<'w, SearchBudget>,
+}
+
+/// A move a [`Bot`] is searching on the [`AsyncComputeTaskPool`]
+pub struct PendingBotMove {
+    task: Task<(Option<BoardCoordinates>, StdRng)>,
+    /// Seconds since the search started
+    waited: f32,
+}
+
+impl PendingBotMove {
+    /// Start searching on a copy of the position, with a copy of the [`BotRng`] that replaces it
+    /// once the move is played
+    fn spawn(bot_seat: &BotSeat, view: &BotView, rng: &BotRng) -> Self {
+        let bot = bot_seat.bot.clone();
+        let position = BotPosition::new(view);
+        let mut rng = rng.0.clone();
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            let bot = &mut *bot.lock().unwrap_or_else(PoisonError::into_inner);
+            let tile = choose_legal_move(bot.as_mut(), &position.view(), &mut rng);
+            (tile, rng)
+        });
+        Self { task, waited: 0.0 }
+    }
+}
+
+/// System to let the [`Bot`] whose turn it is place its card, the same way players do. The bot
+/// searches off the main thread, its move is played once found and [`BOT_MOVE_SECONDS`] passed.
+/// A search is dropped if the board or the seats change in the meantime, e.g. after an undo
+pub fn play_bot_move(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pending: Local<Option<PendingBotMove>>,
+    mut bots: BotContext,
+    mut context: PlaceCardContext,
+) {
+    if context.board_state.is_changed()
+        || context.card_index.is_changed()
+        || bots.seats.is_changed()
+    {
+        *pending = None;
+    }
+
+    let index = context.card_index.index;
+    let seat = bots.seats.seat_for_turn(index);
+    let Some(bot_seat) = bots.seats.bot_for_turn(index) else {
+        *pending = None;
+        return;
+    };
+    let Some(card) = get_next_card(&context.card_index, &context.card_sequence) else {
+        return;
+    };
+    if winning_line(&context.board_state).is_some() {
+        return;
+    }
+
+    let pending_move = pending.get_or_insert_with(|| {
+        let players = bots.deck.players();
+        let view = BotView {
+            board_state: &context.board_state,
+            card,
+            board_limit: *context.board_limit,
+            seat,
+            players: &players,
+            deck: &bots.deck,
+            played: &context.card_sequence.cards[..index],
+            budget: *bots.budget,
+        };
+        PendingBotMove::spawn(bot_seat, &view, &bots.rng)
+    });
+    pending_move.waited += time.delta_seconds();
+    if pending_move.waited < BOT_MOVE_SECONDS {
+        return;
+    }
+    let Some((chosen, rng)) = block_on(poll_once(&mut pending_move.task)) else {
+        return;
+    };
+    *pending = None;
+    bots.rng.0 = rng;
+
+    let placed = match chosen {
+        Some(spawn_coordinates) => context.place_next_card(&mut commands, spawn_coordinates),
+        None => false,
+    };
+    // Skip a card that fits nowhere, or the bot would wait for it forever
+    if !placed {
+        warn!(
+            "{} bot has no legal move for {}, skipping it",
+            bot_seat.setup, card
+        );
+        context.skip_next_card();
+    }
+}
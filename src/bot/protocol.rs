@@ -0,0 +1,312 @@
+//! Line based text protocol between the game and external engines, similar to UCI for chess
+//!
+//! The game writes one command per line to the standard input of the engine and reads its
+//! answers from the standard output. Cards, tiles and positions use the
+//! [`notation`](crate::notation). A single move looks like:
+//!
+//! ```text
+//! > pount
+//! < id name greedy
+//! < pountok
+//! > newgame
+//! > players RY BG
+//! > limit 4
+//! > position 10/10/10/10/10/10/3,Y4,6/2,R3G7,7/10/10 3 1
+//! > card G7
+//! > go playouts 1000 movetime 500
+//! < bestmove c4
+//! > quit
+//! ```
+//!
+//! `limit` is the size of the square all cards have to fit within, 10 when it is not sent.
+//! `bestmove none` means the card fits nowhere. Engines should ignore commands and answers they do
+//! not know, and may print `info <anything>` lines while thinking.
+
+use std::{fmt, str::FromStr};
+
+use crate::{
+    board::bundle::{BoardLimit, GameState, BOARD_SIZE},
+    card::bundle::{Card, CardColor},
+    coordinates::BoardCoordinates,
+    notation::NotationError,
+};
+
+/// Error returned when a line is not a valid command or answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProtocolError {
+    Unknown(String),
+    MissingArgument(&'static str),
+    InvalidArgument(String),
+    Notation(NotationError),
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolError::Unknown(line) => write!(f, "unknown command '{}'", line),
+            ProtocolError::MissingArgument(argument) => write!(f, "missing {}", argument),
+            ProtocolError::InvalidArgument(argument) => {
+                write!(f, "invalid argument '{}'", argument)
+            }
+            ProtocolError::Notation(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ProtocolError {}
+
+impl From<NotationError> for ProtocolError {
+    fn from(err: NotationError) -> Self {
+        ProtocolError::Notation(err)
+    }
+}
+
+/// Command sent from the game to an engine
+#[derive(Clone)]
+pub enum EngineCommand {
+    /// Start of the conversation, the engine answers with its id and `pountok`
+    Pount,
+    /// The engine answers `readyok` once it is done with all previous commands
+    IsReady,
+    /// The next position is from a different game
+    NewGame,
+    /// Colors held by each player, in turn order
+    Players(Vec<Vec<CardColor>>),
+    /// Square all cards have to fit within
+    Limit(BoardLimit),
+    /// The board, index of the next card and whose turn it is
+    Position {
+        board_state: Box<GameState>,
+        next_card: usize,
+        turn: usize,
+    },
+    /// The card the engine has to place
+    Card(Card),
+    /// Pick a tile for the card, within this many playouts and milliseconds
+    Go {
+        playouts: usize,
+        movetime: u64,
+    },
+    Quit,
+}
+
+impl fmt::Display for EngineCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EngineCommand::Pount => write!(f, "pount"),
+            EngineCommand::IsReady => write!(f, "isready"),
+            EngineCommand::NewGame => write!(f, "newgame"),
+            EngineCommand::Players(players) => {
+                let players: Vec<String> = players
+                    .iter()
+                    .map(|colors| colors.iter().map(|color| color.letter()).collect())
+                    .collect();
+                write!(f, "players {}", players.join(" "))
+            }
+            EngineCommand::Limit(limit) => write!(f, "limit {}", limit.0),
+            EngineCommand::Position {
+                board_state,
+                next_card,
+                turn,
+            } => write!(f, "position {}", board_state.to_notation(*next_card, *turn)),
+            EngineCommand::Card(card) => write!(f, "card {}", card),
+            EngineCommand::Go { playouts, movetime } => {
+                write!(f, "go playouts {} movetime {}", playouts, movetime)
+            }
+            EngineCommand::Quit => write!(f, "quit"),
+        }
+    }
+}
+
+impl FromStr for EngineCommand {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (command, arguments) = s.split_once(' ').unwrap_or((s, ""));
+        let arguments = arguments.trim();
+
+        match command {
+            "pount" => Ok(EngineCommand::Pount),
+            "isready" => Ok(EngineCommand::IsReady),
+            "newgame" => Ok(EngineCommand::NewGame),
+            "players" => arguments
+                .split_whitespace()
+                .map(|colors| colors.chars().map(CardColor::from_letter).collect())
+                .collect::<Result<_, _>>()
+                .map(EngineCommand::Players)
+                .map_err(ProtocolError::from),
+            "limit" => match arguments.parse() {
+                Ok(limit) if (1..=BOARD_SIZE).contains(&limit) => {
+                    Ok(EngineCommand::Limit(BoardLimit(limit)))
+                }
+                _ if arguments.is_empty() => Err(ProtocolError::MissingArgument("limit")),
+                _ => Err(ProtocolError::InvalidArgument(arguments.to_string())),
+            },
+            "position" => {
+                let (board_state, next_card, turn) = GameState::from_notation(arguments)?;
+                Ok(EngineCommand::Position {
+                    board_state: Box::new(board_state),
+                    next_card,
+                    turn,
+                })
+            }
+            "card" => Ok(EngineCommand::Card(arguments.parse()?)),
+            "go" => parse_go(arguments),
+            "quit" => Ok(EngineCommand::Quit),
+            _ => Err(ProtocolError::Unknown(s.to_string())),
+        }
+    }
+}
+
+/// Parse the `name value` pairs after `go`, missing limits are unlimited
+fn parse_go(arguments: &str) -> Result<EngineCommand, ProtocolError> {
+    let mut playouts = usize::MAX;
+    let mut movetime = u64::MAX;
+
+    let mut tokens = arguments.split_whitespace();
+    while let Some(name) = tokens.next() {
+        let value = tokens
+            .next()
+            .ok_or(ProtocolError::MissingArgument("value"))?;
+        let invalid = || ProtocolError::InvalidArgument(value.to_string());
+        match name {
+            "playouts" => playouts = value.parse().map_err(|_| invalid())?,
+            "movetime" => movetime = value.parse().map_err(|_| invalid())?,
+            _ => return Err(ProtocolError::InvalidArgument(name.to_string())),
+        }
+    }
+
+    Ok(EngineCommand::Go { playouts, movetime })
+}
+
+/// Answer sent from an engine to the game
+#[derive(Debug, Clone)]
+pub enum EngineResponse {
+    Id {
+        name: String,
+    },
+    PountOk,
+    ReadyOk,
+    /// The chosen tile, [`None`] if the card fits nowhere
+    BestMove(Option<BoardCoordinates>),
+    /// Anything the engine wants to show while thinking
+    Info(String),
+}
+
+impl fmt::Display for EngineResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EngineResponse::Id { name } => write!(f, "id name {}", name),
+            EngineResponse::PountOk => write!(f, "pountok"),
+            EngineResponse::ReadyOk => write!(f, "readyok"),
+            EngineResponse::BestMove(Some(tile)) => write!(f, "bestmove {}", tile),
+            EngineResponse::BestMove(None) => write!(f, "bestmove none"),
+            EngineResponse::Info(info) => write!(f, "info {}", info),
+        }
+    }
+}
+
+impl FromStr for EngineResponse {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (response, arguments) = s.split_once(' ').unwrap_or((s, ""));
+        let arguments = arguments.trim();
+
+        match response {
+            "id" => arguments
+                .strip_prefix("name")
+                .map(|name| EngineResponse::Id {
+                    name: name.trim().to_string(),
+                })
+                .ok_or(ProtocolError::MissingArgument("name")),
+            "pountok" => Ok(EngineResponse::PountOk),
+            "readyok" => Ok(EngineResponse::ReadyOk),
+            "bestmove" => match arguments {
+                "" => Err(ProtocolError::MissingArgument("tile")),
+                "none" => Ok(EngineResponse::BestMove(None)),
+                tile => Ok(EngineResponse::BestMove(Some(tile.parse()?))),
+            },
+            "info" => Ok(EngineResponse::Info(arguments.to_string())),
+            _ => Err(ProtocolError::Unknown(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command_round_trip(line: &str) {
+        assert_eq!(line.parse::<EngineCommand>().unwrap().to_string(), line);
+    }
+
+    fn response_round_trip(line: &str) {
+        assert_eq!(line.parse::<EngineResponse>().unwrap().to_string(), line);
+    }
+
+    #[test]
+    fn commands_round_trip() {
+        for line in [
+            "pount",
+            "isready",
+            "newgame",
+            "players RY BG",
+            "players R Y B",
+            "limit 4",
+            "limit 10",
+            "position 10/10/10/10/10/10/3,Y4,6/2,R3G7,7/10/10 3 1",
+            "card G7",
+            "go playouts 1000 movetime 500",
+            "quit",
+        ] {
+            command_round_trip(line);
+        }
+    }
+
+    #[test]
+    fn go_limits_default_to_unlimited() {
+        let Ok(EngineCommand::Go { playouts, movetime }) = "go movetime 20".parse() else {
+            panic!("go should parse");
+        };
+        assert_eq!((playouts, movetime), (usize::MAX, 20));
+    }
+
+    #[test]
+    fn rejects_bad_commands() {
+        for line in [
+            "",
+            "start",
+            "players RX",
+            "limit",
+            "limit 0",
+            "limit 11",
+            "limit four",
+            "position 10/10 0 0",
+            "card G0",
+            "go playouts",
+            "go depth 3",
+        ] {
+            assert!(line.parse::<EngineCommand>().is_err(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn responses_round_trip() {
+        for line in [
+            "id name pount greedy",
+            "pountok",
+            "readyok",
+            "bestmove c4",
+            "bestmove none",
+            "info depth 3 score 0.5",
+        ] {
+            response_round_trip(line);
+        }
+        for line in ["", "bestmove", "bestmove k1", "id", "ok"] {
+            assert!(line.parse::<EngineResponse>().is_err(), "{}", line);
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use rand::rngs::StdRng;
+
+use crate::coordinates::BoardCoordinates;
+
+use super::strategy::{Bot, BotView};
+
+/// Places its card on a random legal tile
+pub struct RandomBot;
+
+impl Bot for RandomBot {
+    /// Every tile is as good as any other, so the best move is a random one
+    fn score_moves(&mut self, view: &BotView, _rng: &mut StdRng) -> Vec<(BoardCoordinates, f32)> {
+        view.legal_moves()
+            .into_iter()
+            .map(|coordinates| (coordinates, 0.0))
+            .collect()
+    }
+}
@@ -0,0 +1,238 @@
+use std::{fmt, time::Duration};
+
+use bevy::prelude::*;
+use clap::ValueEnum;
+use rand::{rngs::StdRng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    board::bundle::{BoardLimit, GameState},
+    card::{
+        bundle::{Card, CardColor},
+        deck::DeckDefinition,
+        spawn::legal_moves,
+    },
+    coordinates::BoardCoordinates,
+};
+
+use super::{
+    difficulty::{Difficulty, HandicappedBot},
+    greedy::GreedyBot,
+    mcts::MctsBot,
+    random::RandomBot,
+};
+
+/// How long searching bots may think about a single move, whichever runs out first
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct SearchBudget {
+    pub playouts: usize,
+    pub time_limit: Duration,
+}
+
+impl Default for SearchBudget {
+    fn default() -> Self {
+        Self {
+            playouts: 1000,
+            time_limit: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Everything a [`Bot`] gets to see when it is its turn. The order of the cards still to come is
+/// deliberately not part of it
+#[derive(Clone, Copy)]
+pub struct BotView<'a> {
+    pub board_state: &'a GameState,
+    /// The card the bot has to place
+    pub card: Card,
+    pub board_limit: BoardLimit,
+    /// Seat of the bot, index into `players`
+    pub seat: usize,
+    /// Colors held by each player, in turn order
+    pub players: &'a [Vec<CardColor>],
+    /// The deck all cards were dealt from
+    pub deck: &'a DeckDefinition,
+    /// Every card placed so far, in turn order
+    pub played: &'a [Card],
+    pub budget: SearchBudget,
+}
+
+impl BotView<'_> {
+    /// Every tile the card can legally be placed on
+    pub fn legal_moves(&self) -> Vec<BoardCoordinates> {
+        legal_moves(&self.card, self.board_state, self.board_limit)
+    }
+
+    /// Colors held by the other players
+    pub fn opponent_colors(&self) -> impl Iterator<Item = CardColor> + '_ {
+        self.players
+            .iter()
+            .enumerate()
+            .filter(move |(seat, _)| *seat != self.seat)
+            .flat_map(|(_, colors)| colors.iter().copied())
+    }
+}
+
+/// Everything in a [`BotView`], owned so a [`Bot`] can search it on another thread
+#[derive(Clone)]
+pub struct BotPosition {
+    pub board_state: GameState,
+    pub card: Card,
+    pub board_limit: BoardLimit,
+    pub seat: usize,
+    pub players: Vec<Vec<CardColor>>,
+    pub deck: DeckDefinition,
+    pub played: Vec<Card>,
+    pub budget: SearchBudget,
+}
+
+impl BotPosition {
+    pub fn new(view: &BotView) -> Self {
+        Self {
+            board_state: view.board_state.clone(),
+            card: view.card,
+            board_limit: view.board_limit,
+            seat: view.seat,
+            players: view.players.to_vec(),
+            deck: view.deck.clone(),
+            played: view.played.to_vec(),
+            budget: view.budget,
+        }
+    }
+
+    pub fn view(&self) -> BotView<'_> {
+        BotView {
+            board_state: &self.board_state,
+            card: self.card,
+            board_limit: self.board_limit,
+            seat: self.seat,
+            players: &self.players,
+            deck: &self.deck,
+            played: &self.played,
+            budget: self.budget,
+        }
+    }
+}
+
+/// A computer player
+pub trait Bot: Send + Sync {
+    /// Score every legal tile for [`BotView::card`], higher is better
+    fn score_moves(&mut self, view: &BotView, rng: &mut StdRng) -> Vec<(BoardCoordinates, f32)>;
+
+    /// Pick the tile to place [`BotView::card`] on, [`None`] if it fits nowhere. Takes the best
+    /// scoring tile by default
+    fn choose_move(&mut self, view: &BotView, rng: &mut StdRng) -> Option<BoardCoordinates> {
+        let scored = self.score_moves(view, rng);
+        best_move(scored, rng)
+    }
+}
+
+/// The best scoring tile, ties are broken randomly or a bot always plays into the same corner
+pub fn best_move(
+    scored: Vec<(BoardCoordinates, f32)>,
+    rng: &mut StdRng,
+) -> Option<BoardCoordinates> {
+    let best = scored
+        .iter()
+        .map(|(_, score)| *score)
+        .max_by(f32::total_cmp)?;
+    let best_moves: Vec<_> = scored
+        .into_iter()
+        .filter(|(_, score)| *score == best)
+        .map(|(coordinates, _)| coordinates)
+        .collect();
+    best_moves.choose(rng).cloned()
+}
+
+/// Ask the bot for its tile, falling back to a random legal tile if it picks an illegal one or
+/// none at all. [`None`] only if the card fits nowhere
+pub fn choose_legal_move(
+    bot: &mut dyn Bot,
+    view: &BotView,
+    rng: &mut StdRng,
+) -> Option<BoardCoordinates> {
+    let legal_moves = view.legal_moves();
+    let chosen = bot.choose_move(view, rng);
+    if let Some(tile) = &chosen {
+        if legal_moves
+            .iter()
+            .any(|legal| legal.as_xys() == tile.as_xys())
+        {
+            return chosen;
+        }
+    }
+
+    if !legal_moves.is_empty() {
+        match chosen {
+            Some(tile) => warn!("Bot chose illegal tile {}, playing a random one", tile),
+            None => warn!("Bot found no tile for {}, playing a random one", view.card),
+        }
+    }
+    legal_moves.choose(rng).cloned()
+}
+
+/// Every available [`Bot`], selectable on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
+pub enum BotKind {
+    /// Places on a random legal tile
+    Random,
+    /// Extends its own longest line or blocks the longest line of an opponent
+    Greedy,
+    /// Monte Carlo Tree Search over sampled orders of the cards still to come
+    Mcts,
+    /// An external program speaking the [engine protocol](super::protocol), started with `--engine`
+    #[value(skip)]
+    Engine,
+}
+
+impl BotKind {
+    /// [`None`] for an [`BotKind::Engine`], it needs a command to start, see
+    /// [`BotSeats::seat_engines`](super::plugin::BotSeats::seat_engines)
+    pub fn build(self) -> Option<Box<dyn Bot>> {
+        match self {
+            BotKind::Random => Some(Box::new(RandomBot)),
+            BotKind::Greedy => Some(Box::new(GreedyBot)),
+            BotKind::Mcts => Some(Box::new(MctsBot)),
+            BotKind::Engine => None,
+        }
+    }
+}
+
+impl fmt::Display for BotKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A [`BotKind`] playing at a [`Difficulty`], without one it plays as strong as it can
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BotSetup {
+    pub kind: BotKind,
+    pub difficulty: Option<Difficulty>,
+}
+
+impl BotSetup {
+    pub fn new(kind: BotKind) -> Self {
+        Self {
+            kind,
+            difficulty: None,
+        }
+    }
+
+    pub fn build(self) -> Option<Box<dyn Bot>> {
+        let bot = self.kind.build()?;
+        Some(match self.difficulty {
+            Some(difficulty) => Box::new(HandicappedBot { bot, difficulty }),
+            None => bot,
+        })
+    }
+}
+
+impl fmt::Display for BotSetup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.difficulty {
+            Some(difficulty) => write!(f, "{} ({})", self.kind, difficulty),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
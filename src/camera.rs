@@ -1,9 +1,19 @@
 use bevy::{input::mouse::MouseWheel, prelude::*, render::camera::ScalingMode};
 
-use crate::{board::bundle::TILE_SIZE, schedule::InGameSet};
+use crate::{
+    board::bundle::TILE_SIZE,
+    coordinates::ActuallyLogicalCoordinates,
+    cursor::TileCursor,
+    keys::keymap::{Action, ActionInput},
+    schedule::InGameSet,
+};
 
 const SCROLL_LINE_FACTOR: f32 = 0.2;
 const SCROLL_PIXEL_FACTOR: f32 = 0.2;
+/// Fraction of the remaining distance the camera covers per second while following the [`TileCursor`]
+const FOLLOW_SPEED: f32 = 8.0;
+/// Distance kept between the [`TileCursor`] and the window border
+const FOLLOW_MARGIN: f32 = TILE_SIZE;
 pub const CAMERA_OFFSET_X: f32 = -TILE_SIZE / 2.0;
 pub const CAMERA_OFFSET_Y: f32 = -TILE_SIZE / 2.0;
 
@@ -21,6 +31,7 @@ impl Plugin for CameraPlugin {
                 drag_camera,
                 zoom_control_mouse_scroll,
                 zoom_control_keyboard,
+                follow_tile_cursor,
             )
                 .chain()
                 .in_set(InGameSet::MutateCamera),
@@ -49,7 +60,7 @@ fn spawn_camera(mut commands: Commands) {
 }
 
 fn drag_camera(
-    mouse_input: Res<ButtonInput<MouseButton>>,
+    input: ActionInput,
     mut query: Query<&mut Transform, With<CameraMarker>>,
     windows: Query<&Window>,
     mut last_cursor_position: Local<Option<Vec2>>,
@@ -57,7 +68,7 @@ fn drag_camera(
     let window = windows.single();
 
     if let Some(cursor_position) = window.cursor_position() {
-        if mouse_input.pressed(MouseButton::Left) {
+        if input.pressed(Action::DragCamera) {
             if let Some(last_position) = *last_cursor_position {
                 let delta = cursor_position - last_position;
 
@@ -105,18 +116,46 @@ fn zoom_control_mouse_scroll(
 }
 
 fn zoom_control_keyboard(
-    input: Res<ButtonInput<KeyCode>>,
+    input: ActionInput,
     mut camera_query: Query<&mut OrthographicProjection, With<CameraMarker>>,
 ) {
     let mut projection = camera_query.single_mut();
 
-    if input.pressed(KeyCode::Minus) {
+    if input.pressed(Action::ZoomOut) {
         projection.scale += 0.2;
     }
 
-    if input.pressed(KeyCode::Equal) {
+    if input.pressed(Action::ZoomIn) {
         projection.scale -= 0.2;
     }
 
     projection.scale = projection.scale.clamp(0.2, 5.);
 }
+
+/// Pan the camera until the tile selected by the [`TileCursor`] is inside the window again
+fn follow_tile_cursor(
+    tile_cursor: Res<TileCursor>,
+    time: Res<Time>,
+    windows: Query<&Window>,
+    mut camera_query: Query<(&mut Transform, &OrthographicProjection), With<CameraMarker>>,
+) {
+    let Some(tile) = &tile_cursor.tile else {
+        return;
+    };
+    let window = windows.single();
+    let (mut transform, projection) = camera_query.single_mut();
+
+    // Tile center in world space, the camera translation is the bottom left of the view
+    let coordinates: ActuallyLogicalCoordinates = tile.clone().into();
+    let target = coordinates.transform.translation.truncate();
+    let view = Vec2::new(window.width(), window.height()) * projection.scale;
+    let margin = Vec2::splat(FOLLOW_MARGIN).min(view / 2.0);
+
+    let bottom_left = transform.translation.truncate();
+    let wanted = bottom_left.clamp(target + margin - view, target - margin);
+
+    let factor = (FOLLOW_SPEED * time.delta_seconds()).min(1.0);
+    let position = bottom_left.lerp(wanted, factor);
+    transform.translation.x = position.x;
+    transform.translation.y = position.y;
+}
@@ -1,17 +1,22 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-/// [`Color`]s used in the game, must be multiple of 2
-pub const CARD_COLORS: [Color; 4] = [
-    Color::SALMON,
-    Color::GOLD,
-    Color::AQUAMARINE,
-    Color::SEA_GREEN,
+/// [`CardColor`]s used by the default [`DeckDefinition`](super::deck::DeckDefinition), must be
+/// multiple of 2
+pub const CARD_COLORS: [CardColor; 4] = [
+    CardColor::Red,
+    CardColor::Yellow,
+    CardColor::Blue,
+    CardColor::Green,
 ];
 
 // Compile-time assertion to ensure array length is a multiple of 2
 #[allow(dead_code)]
 const fn assert_multiple_of_two(len: usize) {
-    assert!(len % 2 == 0, "Array length must be a multiple of 2");
+    assert!(
+        len.is_multiple_of(2),
+        "Array length must be a multiple of 2"
+    );
 }
 // Enforce the check by invoking the function in a const context
 const _: () = assert_multiple_of_two(CARD_COLORS.len());
@@ -20,11 +25,44 @@ const _: () = assert_multiple_of_two(CARD_COLORS.len());
 #[derive(Component, Debug)]
 pub struct CardMarker;
 
+/// Color of a [`Card`], each player owns one or more of these
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CardColor {
+    Red,
+    Yellow,
+    Blue,
+    Green,
+    Purple,
+    Orange,
+    /// Not owned by any player, used for special cards
+    Grey,
+}
+
+impl CardColor {
+    /// Neutral cards are not owned by any player and never count towards a line
+    pub fn is_neutral(self) -> bool {
+        self == CardColor::Grey
+    }
+
+    /// The [`Color`] cards of this [`CardColor`] are rendered with
+    pub fn to_color(self) -> Color {
+        match self {
+            CardColor::Red => Color::SALMON,
+            CardColor::Yellow => Color::GOLD,
+            CardColor::Blue => Color::AQUAMARINE,
+            CardColor::Green => Color::SEA_GREEN,
+            CardColor::Purple => Color::VIOLET,
+            CardColor::Orange => Color::ORANGE,
+            CardColor::Grey => Color::SILVER,
+        }
+    }
+}
+
 /// Data component containing a card value and color
-#[derive(Component, Clone, Copy)]
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
 pub struct Card {
     pub value: i32,
-    pub color: Color,
+    pub color: CardColor,
 }
 
 /// Bundle of components required to spawn and render a [`Card`]
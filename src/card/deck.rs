@@ -0,0 +1,235 @@
+use std::{error::Error, fs, path::Path};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::bundle::{Card, CardColor, CARD_COLORS};
+
+/// Directory holding the deck definitions, relative to the working directory
+pub const DECK_DIR: &str = "assets/decks";
+/// Deck definition loaded at startup, relative to the working directory
+pub const DEFAULT_DECK_PATH: &str = "assets/decks/standard.ron";
+
+/// Values of the cards in the default deck, a deck definition may use any positive values
+pub const CARD_VALUES: std::ops::RangeInclusive<i32> = 1..=9;
+const VALUES_PER_COLOR: usize = 2;
+const COLORS_PER_PLAYER: usize = 2;
+/// Fewest players a deck has to have room for in [`PLAYER_COLORS`]
+pub const MIN_PLAYERS: usize = 2;
+/// Colors handed out when the number of players is chosen, in order. Starts with the
+/// [`CARD_COLORS`] of the default deck
+pub const PLAYER_COLORS: [CardColor; 6] = [
+    CardColor::Red,
+    CardColor::Yellow,
+    CardColor::Blue,
+    CardColor::Green,
+    CardColor::Purple,
+    CardColor::Orange,
+];
+
+/// Composition of the deck every [`CardSequence`](super::sequence::CardSequence) is dealt from
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct DeckDefinition {
+    /// Card values dealt for every color
+    pub values: Vec<i32>,
+    /// Number of copies of each value per color
+    pub copies: usize,
+    /// Colors in play, consecutive colors are owned by the same player
+    pub colors: Vec<CardColor>,
+    /// Number of colors each player owns, decides the number of players
+    pub colors_per_player: usize,
+    /// Extra cards dealt to every player on top of their colors
+    #[serde(default)]
+    pub special: Vec<Card>,
+}
+
+impl Default for DeckDefinition {
+    fn default() -> Self {
+        Self {
+            values: CARD_VALUES.collect(),
+            copies: VALUES_PER_COLOR,
+            colors: CARD_COLORS.to_vec(),
+            colors_per_player: COLORS_PER_PLAYER,
+            special: Vec::new(),
+        }
+    }
+}
+
+impl DeckDefinition {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+        let deck: DeckDefinition = ron::from_str(&contents)?;
+        deck.validate()?;
+        Ok(deck)
+    }
+
+    /// Load a deck definition, falling back to the built-in deck if it can not be loaded
+    pub fn load_or_default(path: &Path) -> Self {
+        match DeckDefinition::load(path) {
+            Ok(deck) => {
+                info!("Loaded deck {:?}", path);
+                deck
+            }
+            Err(err) => {
+                warn!("Failed to load deck {:?}, using the default: {}", path, err);
+                DeckDefinition::default()
+            }
+        }
+    }
+
+    /// The same deck dealt to a different number of players, colors are taken from
+    /// [`PLAYER_COLORS`] in order
+    pub fn with_players(mut self, players: usize) -> Result<Self, String> {
+        if players < MIN_PLAYERS {
+            return Err(format!("at least {} players are needed", MIN_PLAYERS));
+        }
+        let num_colors = players * self.colors_per_player;
+        if num_colors > PLAYER_COLORS.len() {
+            return Err(format!(
+                "{} players with {} colors each do not fit {} colors",
+                players,
+                self.colors_per_player,
+                PLAYER_COLORS.len()
+            ));
+        }
+
+        self.colors = PLAYER_COLORS[..num_colors].to_vec();
+        Ok(self)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.values.is_empty() || self.copies == 0 {
+            return Err("deck has no cards".to_string());
+        }
+        if self.colors.is_empty() {
+            return Err("deck has no colors".to_string());
+        }
+        for (index, value) in self.values.iter().enumerate() {
+            if *value < 1 {
+                return Err(format!("card value {} is not positive", value));
+            }
+            if self.values[..index].contains(value) {
+                return Err(format!(
+                    "card value {} is listed twice, use copies instead",
+                    value
+                ));
+            }
+        }
+        if let Some(card) = self.special.iter().find(|card| !card.color.is_neutral()) {
+            return Err(format!("special card {} has to be neutral", card));
+        }
+        if let Some(card) = self.special.iter().find(|card| card.value < 1) {
+            return Err(format!("special card {} is not positive", card));
+        }
+        if self.colors_per_player == 0 || !self.colors.len().is_multiple_of(self.colors_per_player)
+        {
+            return Err(format!(
+                "{} colors can not be split evenly into {} per player",
+                self.colors.len(),
+                self.colors_per_player
+            ));
+        }
+        if self.colors_per_player * MIN_PLAYERS > PLAYER_COLORS.len() {
+            return Err(format!(
+                "{} colors per player leave no room for {} players",
+                self.colors_per_player, MIN_PLAYERS
+            ));
+        }
+        for (index, color) in self.colors.iter().enumerate() {
+            if !PLAYER_COLORS.contains(color) {
+                return Err(format!("{:?} can not be owned by a player", color));
+            }
+            if self.colors[..index].contains(color) {
+                return Err(format!("{:?} is in play twice", color));
+            }
+        }
+        Ok(())
+    }
+
+    /// Colors owned by each player, in turn order
+    pub fn players(&self) -> Vec<Vec<CardColor>> {
+        self.colors
+            .chunks(self.colors_per_player)
+            .map(|colors| colors.to_vec())
+            .collect()
+    }
+
+    pub fn num_players(&self) -> usize {
+        self.colors.len() / self.colors_per_player
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_decks_are_valid() {
+        assert_eq!(DeckDefinition::default().validate(), Ok(()));
+        for entry in fs::read_dir(DECK_DIR).unwrap() {
+            let path = entry.unwrap().path();
+            assert!(DeckDefinition::load(&path).is_ok(), "{:?}", path);
+        }
+    }
+
+    #[test]
+    fn accepts_values_beyond_the_default_deck() {
+        let deck = DeckDefinition {
+            values: vec![1, 5, 12, 20],
+            ..default()
+        };
+        assert_eq!(deck.validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_broken_decks() {
+        let broken = [
+            DeckDefinition {
+                colors: Vec::new(),
+                ..default()
+            },
+            DeckDefinition {
+                values: vec![0, 1, 2],
+                ..default()
+            },
+            DeckDefinition {
+                values: vec![1, 2, 2],
+                ..default()
+            },
+            DeckDefinition {
+                special: vec![Card {
+                    value: 5,
+                    color: CardColor::Red,
+                }],
+                ..default()
+            },
+            DeckDefinition {
+                special: vec![Card {
+                    value: 0,
+                    color: CardColor::Grey,
+                }],
+                ..default()
+            },
+            DeckDefinition {
+                colors: vec![CardColor::Red, CardColor::Red],
+                colors_per_player: 1,
+                ..default()
+            },
+        ];
+        for deck in broken {
+            assert!(deck.validate().is_err(), "{:?}", deck);
+        }
+    }
+
+    #[test]
+    fn deals_to_a_valid_number_of_players() {
+        let deck = DeckDefinition::default();
+        for players in MIN_PLAYERS..=PLAYER_COLORS.len() / deck.colors_per_player {
+            let dealt = deck.clone().with_players(players).unwrap();
+            assert_eq!(dealt.num_players(), players);
+        }
+        for players in [0, 1, PLAYER_COLORS.len() + 1] {
+            assert!(deck.clone().with_players(players).is_err(), "{}", players);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 pub mod bundle;
 pub mod debug;
+pub mod deck;
 pub mod plugin;
 pub mod sequence;
-pub mod show_next;
 pub mod spawn;
 pub mod undo;
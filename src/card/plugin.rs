@@ -1,10 +1,13 @@
 use bevy::prelude::*;
 
-use crate::{board::restart::restart_game, schedule::InGameSet};
+use crate::{
+    board::restart::restart_game,
+    schedule::InGameSet,
+    state::{can_start_game, AppState},
+};
 
 use super::{
     sequence::generate_card_sequences,
-    show_next::{show_infobox, CurrentInfoBox},
     spawn::{spawn_card, CardIndex},
     undo::{undo_last_move, CardHistory},
 };
@@ -14,12 +17,18 @@ pub struct CardPlugin;
 impl Plugin for CardPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(CardIndex::default());
-        app.insert_resource(CurrentInfoBox(None));
         app.insert_resource(CardHistory(None));
         app.add_systems(Startup, generate_card_sequences);
         app.add_systems(
             Update,
-            (spawn_card, undo_last_move, restart_game, show_infobox)
+            (
+                spawn_card.run_if(in_state(AppState::InGame)),
+                undo_last_move
+                    .run_if(in_state(AppState::InGame).or_else(in_state(AppState::GameOver))),
+                restart_game
+                    .run_if(can_start_game)
+                    .run_if(not(in_state(AppState::GameOver))),
+            )
                 .chain()
                 .in_set(InGameSet::MutateBoard),
         );
@@ -1,10 +1,20 @@
 use bevy::prelude::*;
-use rand::prelude::SliceRandom;
+use rand::{prelude::SliceRandom, rngs::StdRng, Rng, SeedableRng};
 
-use super::bundle::{Card, CARD_COLORS};
+use super::{
+    bundle::{Card, CardColor},
+    deck::DeckDefinition,
+};
 
-const CARD_VALUES: std::ops::RangeInclusive<i32> = 1..=9;
-const VALUES_PER_COLOR: usize = 2;
+/// Seed every [`CardSequence`] shuffle is derived from, the same seed always deals the same cards
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameSeed(pub u64);
+
+impl GameSeed {
+    pub fn random() -> Self {
+        GameSeed(rand::random())
+    }
+}
 
 /// A Sequence of [`Card`] used to draw from each turn
 #[derive(Debug, Resource, Default)]
@@ -14,13 +24,11 @@ pub struct CardSequence {
 
 impl CardSequence {
     /// Generate a sequence of cards of given color with randomized values
-    fn generate_color(color: Color) -> Vec<Card> {
-        let mut rng = rand::thread_rng();
-
-        // Duplicate the range so we get the right number of values per color
+    fn generate_color(color: CardColor, deck: &DeckDefinition, rng: &mut impl Rng) -> Vec<Card> {
+        // Duplicate the values so we get the right number of values per color
         let mut numbers: Vec<i32> = Vec::new();
-        for _ in 0..VALUES_PER_COLOR {
-            numbers.extend(CARD_VALUES.clone());
+        for _ in 0..deck.copies {
+            numbers.extend(deck.values.iter().copied());
         }
 
         // Map the range into a sequence of [`Card`]
@@ -30,38 +38,54 @@ impl CardSequence {
             .collect();
 
         // Randomize the [`CardSequence`]
-        cards.shuffle(&mut rng);
+        cards.shuffle(rng);
 
         cards
     }
 
-    /// Generate a CardSequence for a single player
-    pub fn generate_player_sequence(color_1: Color, color_2: Color) -> Self {
-        let mut rng = rand::thread_rng();
+    /// Generate a CardSequence for a single player owning the given colors
+    pub fn generate_player_sequence(
+        colors: &[CardColor],
+        deck: &DeckDefinition,
+        rng: &mut impl Rng,
+    ) -> Self {
         let mut sequence = CardSequence::default();
 
-        for card in Self::generate_color(color_1) {
-            sequence.cards.push(card);
-        }
-        for card in Self::generate_color(color_2) {
-            sequence.cards.push(card);
+        for color in colors {
+            for card in Self::generate_color(*color, deck, rng) {
+                sequence.cards.push(card);
+            }
         }
+        sequence.cards.extend(deck.special.iter().copied());
 
-        sequence.cards.shuffle(&mut rng);
+        sequence.cards.shuffle(rng);
         sequence
     }
 
-    /// Generate a CardSequence
-    pub fn generate_full_sequence() -> Self {
+    /// Generate a CardSequence from the [`DeckDefinition`], shuffled deterministically from the
+    /// [`GameSeed`]
+    pub fn generate_full_sequence(deck: &DeckDefinition, seed: GameSeed) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed.0);
         let mut sequences: Vec<CardSequence> = Vec::new();
 
-        for chunk in CARD_COLORS.chunks(2) {
-            sequences.push(CardSequence::generate_player_sequence(chunk[0], chunk[1]));
+        for colors in deck.players() {
+            sequences.push(CardSequence::generate_player_sequence(
+                &colors, deck, &mut rng,
+            ));
         }
 
         CardSequence::flatten_interleaved(sequences)
     }
 
+    /// Cards each of `num_players` seats has yet to place from `card_index` on, in turn order
+    pub fn cards_left(&self, card_index: usize, num_players: usize) -> Vec<usize> {
+        let mut cards_left = vec![0; num_players];
+        for turn in card_index..self.cards.len() {
+            cards_left[turn % num_players] += 1;
+        }
+        cards_left
+    }
+
     /// Turn a [`Vec<CardSequence>`] into a [`CardSequence`] by interleaving each [`CardSequence`] in the [`Vec`]
     fn flatten_interleaved(vec: Vec<CardSequence>) -> CardSequence {
         let mut out = CardSequence::default();
@@ -81,9 +105,15 @@ impl CardSequence {
 }
 
 /// System to generate a new [`CardSequence`]
-pub fn generate_card_sequences(mut commands: Commands) {
-    let sequence = CardSequence::generate_full_sequence();
-    // TODO extend this for different number of players
-    info!("generated card sequence {:?}", sequence);
+pub fn generate_card_sequences(
+    mut commands: Commands,
+    deck: Res<DeckDefinition>,
+    game_seed: Res<GameSeed>,
+) {
+    let sequence = CardSequence::generate_full_sequence(&deck, *game_seed);
+    info!(
+        "generated card sequence {:?} from {:?}",
+        sequence, *game_seed
+    );
     commands.insert_resource(sequence);
 }
@@ -9,15 +9,18 @@ const CARD_STACK_OFFSET: f32 = 5.0;
 use crate::{
     asset_loader::AssetStore,
     board::{
-        bundle::{GameState, BOARD_SIZE},
+        bundle::{BoardLimit, GameState, BOARD_SIZE},
         win_condition::NEIGHBOURS,
     },
+    bot::plugin::BotSeats,
     card::{
         bundle::{Card, CardBundle, CardMarker},
         undo::PlacedCard,
     },
     coordinates::{ActuallyLogicalCoordinates, BoardCoordinates, LogicalCoordinates},
-    keys::KeyMap,
+    cursor::TileCursor,
+    keys::keymap::{Action, ActionInput},
+    notation::Move,
 };
 
 use super::{sequence::CardSequence, undo::CardHistory};
@@ -32,54 +35,121 @@ pub struct CardIndex {
 #[derive(Component, Debug)]
 pub struct TextMarker;
 
-/// Group of all system parameters used to spawn the next card
+/// Group of all system parameters used to place the next card, shared by players and bots
 #[derive(SystemParam)]
-pub struct SpawnCardContext<'w, 's> {
-    pub keymap: Res<'w, KeyMap>,
-    pub windows: Query<'w, 's, &'static Window>,
+pub struct PlaceCardContext<'w> {
     pub card_index: ResMut<'w, CardIndex>,
     pub board_state: ResMut<'w, GameState>,
     pub asset_store: Res<'w, AssetStore>,
     pub card_sequence: Res<'w, CardSequence>,
     pub card_history: ResMut<'w, CardHistory>,
-    pub keyboard_input: Res<'w, ButtonInput<KeyCode>>,
+    pub board_limit: Res<'w, BoardLimit>,
 }
 
-/// Spawns the next [`Card`] in the [`CardSequence`] on the tile closest to the current cursor location
-pub fn spawn_card(mut commands: Commands, mut context: SpawnCardContext) {
-    if should_spawn_card(&context.keyboard_input, &context.keymap) {
-        let window = context.windows.single();
-
-        // Get cursor position if cursor is in game window
-        if let Some(cursor_position) = window.cursor_position() {
-            let spawn_coordinates = cursor_position_to_boardcoordinates(cursor_position, window);
-
-            // Get the next card in the sequence, if there is any left
-            if let Some(next_card) = get_next_card(&context.card_index, &context.card_sequence) {
-                if valid_spawn_location(&spawn_coordinates, &next_card, &context.board_state) {
-                    update_board_state(
-                        &spawn_coordinates,
-                        &next_card,
-                        &mut context.board_state,
-                        &mut context.card_index,
-                    );
-
-                    render_next_card(
-                        spawn_coordinates,
-                        next_card,
-                        &context.board_state,
-                        &context.asset_store,
-                        &mut commands,
-                        &mut context.card_history,
-                    );
-                }
+impl PlaceCardContext<'_> {
+    /// Place the next [`Card`] in the [`CardSequence`] at the given [`BoardCoordinates`], returns
+    /// false if there is no card left or the location is not valid
+    pub fn place_next_card(
+        &mut self,
+        commands: &mut Commands,
+        spawn_coordinates: BoardCoordinates,
+    ) -> bool {
+        // Get the next card in the sequence, if there is any left
+        let Some(next_card) = get_next_card(&self.card_index, &self.card_sequence) else {
+            return false;
+        };
+        if !valid_spawn_location(
+            &spawn_coordinates,
+            &next_card,
+            &self.board_state,
+            *self.board_limit,
+        ) {
+            return false;
+        }
+
+        info!(
+            "Placed {}",
+            Move {
+                card: next_card,
+                coordinates: Some(spawn_coordinates.clone()),
             }
+        );
+        update_board_state(
+            &spawn_coordinates,
+            &next_card,
+            &mut self.board_state,
+            &mut self.card_index,
+        );
+
+        render_next_card(
+            spawn_coordinates,
+            next_card,
+            &self.board_state,
+            &self.asset_store,
+            commands,
+            &mut self.card_history,
+        );
+        true
+    }
+
+    /// Pass over the next [`Card`] and note it in the [`CardHistory`], see [`skip_next_card`]
+    pub fn skip_next_card(&mut self) {
+        skip_next_card(&mut self.board_state, &mut self.card_index);
+        record_skipped_card(&mut self.card_history);
+    }
+}
+
+/// Group of all system parameters used to spawn the next card
+#[derive(SystemParam)]
+pub struct SpawnCardContext<'w, 's> {
+    pub input: ActionInput<'w>,
+    pub windows: Query<'w, 's, &'static Window>,
+    pub tile_cursor: Res<'w, TileCursor>,
+    pub bot_seats: Res<'w, BotSeats>,
+    pub place: PlaceCardContext<'w>,
+}
+
+/// Spawns the next [`Card`] in the [`CardSequence`] on the tile selected by the [`TileCursor`], or
+/// else on the tile closest to the current mouse cursor location. A card without any legal tile is
+/// skipped
+pub fn spawn_card(mut commands: Commands, mut context: SpawnCardContext) {
+    // Bots place their own cards
+    if context
+        .bot_seats
+        .is_bot_turn(context.place.card_index.index)
+    {
+        return;
+    }
+
+    // Pass over a card that fits nowhere, or the game would wait for it forever
+    let place = &mut context.place;
+    if let Some(next_card) = get_next_card(&place.card_index, &place.card_sequence) {
+        if legal_moves(&next_card, &place.board_state, *place.board_limit).is_empty() {
+            info!("No legal move for {}, skipping it", next_card);
+            place.skip_next_card();
+            return;
+        }
+    }
+
+    if should_spawn_card(&context.input) {
+        // Prefer the tile cursor, otherwise get cursor position if cursor is in game window
+        let spawn_coordinates = context.tile_cursor.tile.clone().or_else(|| {
+            let window = context.windows.get_single().ok()?;
+            window
+                .cursor_position()
+                .map(|cursor_position| cursor_position_to_boardcoordinates(cursor_position, window))
+        });
+
+        if let Some(spawn_coordinates) = spawn_coordinates {
+            context
+                .place
+                .place_next_card(&mut commands, spawn_coordinates);
         }
     }
 }
 
 /// Render the next [`Card`] in the [`CardSequence`] and update the [`CardHistory`]
-fn render_next_card(
+pub fn render_next_card(
     spawn_coordinates: BoardCoordinates,
     next_card: Card,
     board_state: &GameState,
@@ -108,15 +178,19 @@ fn update_card_history(
         entity,
         board_coordinates,
     };
-    if card_history.0.is_some() {
-        card_history.0.as_mut().unwrap().push(last_card);
-    } else {
-        card_history.0 = Some(vec![last_card]);
-    }
+    card_history
+        .0
+        .get_or_insert_with(Vec::new)
+        .push(Some(last_card));
+}
+
+/// Note in the [`CardHistory`] that the next [`Card`] was skipped, see [`skip_next_card`]
+pub fn record_skipped_card(card_history: &mut CardHistory) {
+    card_history.0.get_or_insert_with(Vec::new).push(None);
 }
 
 /// Offset the given [`BoardCoordinates`] in the (x,y) dimension based on the number of cards already present on the [`Tile`]
-fn handle_cardstack_offset(
+pub fn handle_cardstack_offset(
     spawn_coordinates: &BoardCoordinates,
     board_state: &GameState,
 ) -> ActuallyLogicalCoordinates {
@@ -137,7 +211,7 @@ fn handle_cardstack_offset(
 }
 
 /// Update the [`GameState`] with this new [`Card`] and increment [`CardIndex`]
-fn update_board_state(
+pub fn update_board_state(
     spawn_coordinates: &BoardCoordinates,
     next_card: &Card,
     board_state: &mut GameState,
@@ -148,6 +222,13 @@ fn update_board_state(
     card_index.index += 1;
 }
 
+/// Pass over the next [`Card`] without placing it, when it fits nowhere on the board. Marks the
+/// [`GameState`] changed so the game goes on as after a placed card
+pub fn skip_next_card(board_state: &mut ResMut<GameState>, card_index: &mut CardIndex) {
+    card_index.index += 1;
+    board_state.set_changed();
+}
+
 /// Return the tile in [`BoardCoordinates`] that the cursor is hovering over, snapping to the nearest tile
 fn cursor_position_to_boardcoordinates(cursor_position: Vec2, window: &Window) -> BoardCoordinates {
     let logical_coordinates = LogicalCoordinates::from_cursor_position(cursor_position);
@@ -170,7 +251,7 @@ pub fn render_card(
                 card,
                 sprite: SpriteBundle {
                     sprite: Sprite {
-                        color: card.color,
+                        color: card.color.to_color(),
                         custom_size: Some(CARD_DIMENSIONS),
                         ..Default::default()
                     },
@@ -203,29 +284,48 @@ pub fn render_card(
     entity
 }
 
+/// Every tile the [`Card`] can legally be placed on
+pub fn legal_moves(
+    card: &Card,
+    board_state: &GameState,
+    board_limit: BoardLimit,
+) -> Vec<BoardCoordinates> {
+    (0..BOARD_SIZE)
+        .flat_map(|y| (0..BOARD_SIZE).map(move |x| BoardCoordinates::from_xyz(x, y, 0)))
+        .filter(|coordinates| valid_spawn_location(coordinates, card, board_state, board_limit))
+        .collect()
+}
+
 /// Is the hovered tile a valid location to spawn a new card on?
-fn valid_spawn_location(
+pub fn valid_spawn_location(
     spawn_coordinates: &BoardCoordinates,
     next_card: &Card,
     board_state: &GameState,
+    board_limit: BoardLimit,
 ) -> bool {
     // First card is always valid
     if board_state.empty {
-        info!("valid spawn location: board empty");
+        debug!("valid spawn location: board empty");
         return true;
     }
 
     let (x, y, _) = spawn_coordinates.as_xys();
+    // Would the cards outgrow the board limit?
+    if !board_state.fits_within(x, y, board_limit) {
+        debug!("invalid spawn location: outside of {:?}", board_limit);
+        return false;
+    }
+
     // Is there already a Card on this location, with lower value?
     if let Some(top_card) = board_state.get_tile(x, y).cards.last() {
         if next_card.value > top_card.value {
-            info!("valid spawn location: {:?} > {:?}", next_card, top_card);
+            debug!("valid spawn location: {} > {}", next_card, top_card);
             return true;
         } else {
             return false;
         }
     } else {
-        info!("no top card on current spawn position");
+        debug!("no top card on current spawn position");
     }
 
     // Is there a card in on the neighbouring spots for this spawn location
@@ -240,7 +340,7 @@ fn valid_spawn_location(
                     let ny = ny as usize;
 
                     if !board_state.get_tile(nx, ny).cards.is_empty() {
-                        info!(
+                        debug!(
                             "valid spawn location: {:?} at ({},{})",
                             board_state.get_tile(nx, ny).cards.last(),
                             nx,
@@ -256,17 +356,11 @@ fn valid_spawn_location(
 }
 
 /// Get next [`Card`] in the [`CardSequence`]
-fn get_next_card(card_index: &CardIndex, card_sequence: &Res<CardSequence>) -> Option<Card> {
+pub fn get_next_card(card_index: &CardIndex, card_sequence: &CardSequence) -> Option<Card> {
     card_sequence.cards.get(card_index.index).cloned()
 }
 
 /// Should a new [`Card`] be spawned?
-fn should_spawn_card(keyboard_input: &Res<ButtonInput<KeyCode>>, keymap: &Res<KeyMap>) -> bool {
-    keyboard_input.just_pressed(
-        keymap
-            .0
-            .get("spawn")
-            .cloned()
-            .expect("Spawn keymap not found"),
-    )
+fn should_spawn_card(input: &ActionInput) -> bool {
+    input.just_pressed(Action::Spawn)
 }
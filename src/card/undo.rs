@@ -2,8 +2,9 @@ use bevy::{ecs::system::SystemParam, prelude::*};
 
 use crate::{
     board::{bundle::GameState, win_condition::PlayerWinEntity},
+    bot::plugin::BotSeats,
     coordinates::BoardCoordinates,
-    keys::KeyMap,
+    keys::keymap::{Action, ActionInput},
 };
 
 use super::spawn::CardIndex;
@@ -13,8 +14,10 @@ pub struct PlacedCard {
     pub entity: Entity,
 }
 
+/// What happened to every dealt card so far, in turn order. [`None`] for a card that was skipped
+/// because it fit nowhere
 #[derive(Resource)]
-pub struct CardHistory(pub Option<Vec<PlacedCard>>);
+pub struct CardHistory(pub Option<Vec<Option<PlacedCard>>>);
 
 #[derive(SystemParam)]
 pub struct UndoContext<'w> {
@@ -22,38 +25,53 @@ pub struct UndoContext<'w> {
     pub board_state: ResMut<'w, GameState>,
     pub card_history: ResMut<'w, CardHistory>,
     pub player_win_entity: ResMut<'w, PlayerWinEntity>,
-    pub keymap: Res<'w, KeyMap>,
+    pub bot_seats: Res<'w, BotSeats>,
+    pub input: ActionInput<'w>,
 }
 
-pub fn undo_last_move(
-    mut commands: Commands,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut context: UndoContext,
-) {
-    if should_undo(&keyboard_input, &context.keymap) {
-        if let Some(placed_cards) = context.card_history.0.as_mut() {
-            handle_undo(
-                &mut commands,
-                placed_cards,
-                &mut context.board_state,
-                &mut context.player_win_entity,
-                &mut context.card_index,
-            );
+/// Take back moves until it is a person's turn again, otherwise a bot would only play its
+/// move again
+pub fn undo_last_move(mut commands: Commands, mut context: UndoContext) {
+    if !should_undo(&context.input) {
+        return;
+    }
+    let Some(placed_cards) = context.card_history.0.as_mut() else {
+        return;
+    };
+
+    loop {
+        handle_undo(
+            &mut commands,
+            placed_cards,
+            &mut context.board_state,
+            &mut context.player_win_entity,
+            &mut context.card_index,
+        );
+        let bot_to_move = context.bot_seats.has_person()
+            && context.bot_seats.is_bot_turn(context.card_index.index);
+        if !bot_to_move || !placed_cards.iter().any(Option::is_some) {
+            break;
         }
     }
 }
 
-fn handle_undo(
+/// Take back the last placed card. Cards skipped after it are taken back too, they would only be
+/// skipped again
+pub fn handle_undo(
     commands: &mut Commands,
-    placed_cards: &mut Vec<PlacedCard>,
+    placed_cards: &mut Vec<Option<PlacedCard>>,
     board_state: &mut GameState,
     player_win_entity: &mut PlayerWinEntity,
     card_index: &mut CardIndex,
 ) {
-    if let Some(PlacedCard {
+    while let Some(None) = placed_cards.last() {
+        placed_cards.pop();
+        card_index.index -= 1;
+    }
+    if let Some(Some(PlacedCard {
         board_coordinates,
         entity,
-    }) = placed_cards.pop()
+    })) = placed_cards.pop()
     {
         // despawn player win notification if previous move won the game
         despawn_win_notification(commands, player_win_entity);
@@ -72,7 +90,7 @@ fn handle_undo(
 fn undo_card_placement(
     board_state: &mut GameState,
     board_coordinates: BoardCoordinates,
-    placed_cards: &[PlacedCard],
+    placed_cards: &[Option<PlacedCard>],
 ) {
     let (x, y, _) = board_coordinates.as_xys();
 
@@ -83,7 +101,7 @@ fn undo_card_placement(
         .expect("Attempting to remove the previously placed card, but no card found");
 
     // Undo of the first move: update board state empty flag
-    if placed_cards.is_empty() {
+    if placed_cards.iter().all(Option::is_none) {
         info!("setting board state empty");
         board_state.empty = true;
     }
@@ -97,12 +115,6 @@ fn despawn_win_notification(commands: &mut Commands, player_win_entity: &mut Pla
     }
 }
 
-fn should_undo(keyboard_input: &ButtonInput<KeyCode>, keymap: &KeyMap) -> bool {
-    keyboard_input.just_pressed(
-        keymap
-            .0
-            .get("undo")
-            .cloned()
-            .expect("Undo keymap not found"),
-    )
+fn should_undo(input: &ActionInput) -> bool {
+    input.just_pressed(Action::Undo)
 }
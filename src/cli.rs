@@ -0,0 +1,257 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use clap::Parser;
+
+use crate::{
+    board::bundle::{BoardLimit, BOARD_SIZE},
+    bot::{
+        difficulty::Difficulty,
+        plugin::{BotRng, BotSeats},
+        strategy::{BotKind, BotSetup, SearchBudget},
+    },
+    card::{
+        deck::{DeckDefinition, DECK_DIR, DEFAULT_DECK_PATH},
+        sequence::GameSeed,
+    },
+    daily::DailyChallenge,
+    profile::plugin::SeatProfiles,
+    state::AppState,
+    tourney::bracket::{Tourney, TourneyFormat, TOURNEY_FILE},
+};
+
+/// Launch options, everything not given falls back to what a regular game uses
+#[derive(Parser, Resource, Debug, Clone, Default)]
+#[command(name = "pount", about = "Place cards, get four of your color in a row")]
+pub struct Cli {
+    /// Number of players, each gets the next colors of the deck
+    #[arg(long, conflicts_with_all = ["tourney", "tourney_players"])]
+    pub players: Option<usize>,
+
+    /// Seed the first game is dealt from, random if not given
+    #[arg(long, conflicts_with = "daily")]
+    pub seed: Option<u64>,
+
+    /// Start straight into today's daily challenge, against the daily bot
+    #[arg(long, conflicts_with_all = [
+        "variant",
+        "deck",
+        "players",
+        "board_limit",
+        "bots",
+        "engines",
+        "tourney",
+        "tourney_players",
+    ])]
+    pub daily: bool,
+
+    /// Name of a deck in the decks directory, e.g. `jokers`
+    #[arg(long, conflicts_with = "deck")]
+    pub variant: Option<String>,
+
+    /// Path to a deck definition file
+    #[arg(long)]
+    pub deck: Option<PathBuf>,
+
+    /// All cards have to fit within a square of this many tiles
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..=BOARD_SIZE as u64))]
+    pub board_limit: Option<u64>,
+
+    /// Bot playing the next opponent seat, repeat for every bot
+    #[arg(long = "bot", value_enum)]
+    pub bots: Vec<BotKind>,
+
+    /// Difficulty of the `--bot` at the same position, bots without one play as strong as they can
+    #[arg(long = "difficulty", value_enum, requires = "bots")]
+    pub difficulties: Vec<Difficulty>,
+
+    /// Command starting an external engine for the seat after the `--bot`s, repeat for every
+    /// engine, e.g. `--engine "target/debug/engine"`
+    #[arg(long = "engine")]
+    pub engines: Vec<String>,
+
+    /// Most playouts a searching bot runs per move
+    #[arg(long)]
+    pub playouts: Option<usize>,
+
+    /// Most milliseconds a searching bot thinks per move
+    #[arg(long)]
+    pub think_ms: Option<u64>,
+
+    /// Profile playing the next seat, starting with the first seat. Repeat for every seat
+    #[arg(long = "profile")]
+    pub profiles: Vec<String>,
+
+    /// Register a player for a new tourney, repeat for every player. Replaces the saved tourney
+    #[arg(long = "tourney-player", conflicts_with_all = ["bots", "engines", "tourney"])]
+    pub tourney_players: Vec<String>,
+
+    /// How the matches of a new tourney are paired
+    #[arg(long, value_enum, default_value_t = TourneyFormat::RoundRobin)]
+    pub tourney_format: TourneyFormat,
+
+    /// Games a player has to win to win a tourney match
+    #[arg(long, default_value_t = 2)]
+    pub match_points: u32,
+
+    /// Continue the saved tourney
+    #[arg(long, conflicts_with_all = ["bots", "engines"])]
+    pub tourney: bool,
+
+    /// Replay file to continue playing from its last move
+    #[arg(long)]
+    pub load: Option<PathBuf>,
+
+    /// Run without a window, bots play every seat until the game is over and the result is printed
+    #[arg(long)]
+    pub headless: bool,
+}
+
+impl Cli {
+    /// Insert the resources configured on the command line
+    pub fn insert_resources(&self, app: &mut App) {
+        let game_seed = self.game_seed();
+        // The daily challenge is the same for everyone, whatever deck is on disk
+        let (deck, board_limit) = if self.daily {
+            (DeckDefinition::default(), BoardLimit::default())
+        } else {
+            (self.deck_definition(), self.board_limit())
+        };
+
+        if self.daily {
+            let challenge = DailyChallenge::today();
+            info!("Starting daily challenge of {}", challenge.date);
+            app.insert_resource(challenge);
+            app.insert_resource(DailyChallenge::bot_seats(deck.num_players()));
+        } else if let Some(tourney) = self.tourney() {
+            // Tourney matches are played by the people at the table
+            app.insert_resource(BotSeats::opponents(deck.num_players(), &[]));
+            app.insert_resource(tourney);
+        } else {
+            app.insert_resource(BotSeats::opponents(deck.num_players(), &self.bot_setups()));
+        }
+
+        app.insert_resource(game_seed);
+        app.insert_resource(BotRng::from_game_seed(game_seed));
+        app.insert_resource(deck);
+        app.insert_resource(board_limit);
+        app.insert_resource(self.search_budget());
+        app.insert_resource(SeatProfiles(
+            self.profiles.iter().cloned().map(Some).collect(),
+        ));
+        app.insert_resource(self.clone());
+    }
+
+    /// Straight into the game when the command line sets one up, the main menu otherwise
+    pub fn initial_state(&self) -> AppState {
+        let sets_up_game = self.seed.is_some()
+            || self.daily
+            || self.is_tourney()
+            || self.load.is_some()
+            || !self.bots.is_empty()
+            || !self.engines.is_empty();
+        if sets_up_game {
+            AppState::InGame
+        } else {
+            AppState::MainMenu
+        }
+    }
+
+    pub fn game_seed(&self) -> GameSeed {
+        if self.daily {
+            return DailyChallenge::today().seed();
+        }
+        self.seed.map(GameSeed).unwrap_or_else(GameSeed::random)
+    }
+
+    /// Load the deck given by `--deck` or `--variant`, or the [`DEFAULT_DECK_PATH`], dealt to
+    /// `--players` players
+    pub fn deck_definition(&self) -> DeckDefinition {
+        let path = match (&self.deck, &self.variant) {
+            (Some(path), _) => path.clone(),
+            (None, Some(variant)) => PathBuf::from(DECK_DIR).join(format!("{}.ron", variant)),
+            (None, None) => PathBuf::from(DEFAULT_DECK_PATH),
+        };
+        let deck = DeckDefinition::load_or_default(&path);
+
+        // Tourney matches are always between two players
+        let tourney_players = self.is_tourney().then_some(2);
+        match self.players.or(tourney_players) {
+            Some(players) => deck.clone().with_players(players).unwrap_or_else(|err| {
+                warn!("Ignoring --players {}: {}", players, err);
+                deck
+            }),
+            None => deck,
+        }
+    }
+
+    pub fn is_tourney(&self) -> bool {
+        self.tourney || !self.tourney_players.is_empty()
+    }
+
+    /// A new tourney between the `--tourney-player`s, or the saved tourney with `--tourney`
+    pub fn tourney(&self) -> Option<Tourney> {
+        if !self.tourney_players.is_empty() {
+            let tourney = Tourney::new(
+                self.tourney_players.clone(),
+                self.tourney_format,
+                self.match_points,
+            );
+            return match tourney {
+                Ok(tourney) => {
+                    info!("Starting a {:?} tourney", tourney.format);
+                    tourney.save_to_file();
+                    Some(tourney)
+                }
+                Err(err) => {
+                    warn!("Not starting a tourney: {}", err);
+                    None
+                }
+            };
+        }
+
+        if self.tourney {
+            let path = Path::new(TOURNEY_FILE);
+            return match Tourney::load(path) {
+                Ok(tourney) => Some(tourney),
+                Err(err) => {
+                    warn!("Failed to load tourney {:?}: {}", path, err);
+                    None
+                }
+            };
+        }
+        None
+    }
+
+    /// Every `--bot` with its `--difficulty`, in seat order
+    pub fn bot_setups(&self) -> Vec<BotSetup> {
+        self.bots
+            .iter()
+            .enumerate()
+            .map(|(index, kind)| BotSetup {
+                kind: *kind,
+                difficulty: self.difficulties.get(index).copied(),
+            })
+            .collect()
+    }
+
+    pub fn search_budget(&self) -> SearchBudget {
+        let default = SearchBudget::default();
+        SearchBudget {
+            playouts: self.playouts.unwrap_or(default.playouts),
+            time_limit: self
+                .think_ms
+                .map(Duration::from_millis)
+                .unwrap_or(default.time_limit),
+        }
+    }
+
+    pub fn board_limit(&self) -> BoardLimit {
+        self.board_limit
+            .map(|limit| BoardLimit(limit as usize))
+            .unwrap_or_default()
+    }
+}
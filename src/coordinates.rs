@@ -80,6 +80,17 @@ impl BoardCoordinates {
     pub fn transform(&self) -> Transform {
         self.transform
     }
+
+    /// Coordinates of the tile `dx` columns and `dy` rows away, clamped to the board
+    pub fn offset(&self, dx: i32, dy: i32) -> Self {
+        let (x, y, z) = self.as_xys();
+        let max = BOARD_SIZE as i32 - 1;
+        Self::from_xyz(
+            (x as i32 + dx).clamp(0, max) as usize,
+            (y as i32 + dy).clamp(0, max) as usize,
+            z,
+        )
+    }
 }
 
 impl From<ActuallyLogicalCoordinates> for BoardCoordinates {
@@ -0,0 +1,163 @@
+use bevy::{prelude::*, window::CursorMoved};
+
+use crate::{
+    board::bundle::{BOARD_SIZE, TILE_SIZE},
+    coordinates::{ActuallyLogicalCoordinates, BoardCoordinates},
+    keys::keymap::{Action, ActionInput},
+    schedule::InGameSet,
+};
+
+/// Stick deflection needed before the tile cursor moves
+const STICK_DEADZONE: f32 = 0.5;
+/// Seconds between two tile cursor moves while a stick is held
+const STICK_REPEAT_SECONDS: f32 = 0.2;
+/// Z at which the tile cursor is rendered, above card stacks
+const TILE_CURSOR_Z: f32 = 50.0;
+const TILE_CURSOR_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.35);
+
+/// Tile selected with the keyboard or a gamepad, cards are placed here instead of under the mouse cursor while
+/// it is set. Moving the mouse clears it again
+#[derive(Resource, Default)]
+pub struct TileCursor {
+    pub tile: Option<BoardCoordinates>,
+}
+
+impl TileCursor {
+    /// Move the selected tile, selecting the center of the board if there was none
+    pub fn move_by(&mut self, dx: i32, dy: i32) {
+        self.tile = Some(match &self.tile {
+            Some(tile) => tile.offset(dx, dy),
+            None => BoardCoordinates::from_xyz(BOARD_SIZE / 2, BOARD_SIZE / 2, 0),
+        });
+    }
+}
+
+/// Marker component for the tile cursor highlight
+#[derive(Component)]
+pub struct TileCursorMarker;
+
+pub struct CursorPlugin;
+
+impl Plugin for CursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TileCursor>();
+        app.add_systems(Startup, spawn_tile_cursor);
+        app.add_systems(
+            Update,
+            (
+                move_tile_cursor_buttons,
+                move_tile_cursor_stick,
+                clear_tile_cursor_on_mouse_move,
+                render_tile_cursor,
+            )
+                .chain()
+                .in_set(InGameSet::MoveCursor),
+        );
+    }
+}
+
+fn spawn_tile_cursor(mut commands: Commands) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: TILE_CURSOR_COLOR,
+                custom_size: Some(Vec2::splat(TILE_SIZE)),
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        TileCursorMarker,
+    ));
+}
+
+/// System to move the [`TileCursor`] with the arrow keys, WASD, the d-pad or any other cursor [`Binding`](crate::keys::keymap::Binding)
+fn move_tile_cursor_buttons(input: ActionInput, mut tile_cursor: ResMut<TileCursor>) {
+    let directions = [
+        (Action::CursorUp, (0, 1)),
+        (Action::CursorDown, (0, -1)),
+        (Action::CursorLeft, (-1, 0)),
+        (Action::CursorRight, (1, 0)),
+    ];
+
+    for (action, (dx, dy)) in directions {
+        if input.just_pressed(action) {
+            tile_cursor.move_by(dx, dy);
+        }
+    }
+}
+
+/// System to move the [`TileCursor`] with the left stick of any gamepad, repeating while held
+fn move_tile_cursor_stick(
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    time: Res<Time>,
+    mut tile_cursor: ResMut<TileCursor>,
+    mut cooldown: Local<f32>,
+) {
+    *cooldown = (*cooldown - time.delta_seconds()).max(0.0);
+
+    for gamepad in gamepads.iter() {
+        let stick = |axis_type| {
+            axes.get(GamepadAxis::new(gamepad, axis_type))
+                .unwrap_or_default()
+        };
+        let x = stick(GamepadAxisType::LeftStickX);
+        let y = stick(GamepadAxisType::LeftStickY);
+
+        if x.abs() < STICK_DEADZONE && y.abs() < STICK_DEADZONE {
+            continue;
+        }
+        if *cooldown > 0.0 {
+            return;
+        }
+
+        let direction = |value: f32| {
+            if value >= STICK_DEADZONE {
+                1
+            } else if value <= -STICK_DEADZONE {
+                -1
+            } else {
+                0
+            }
+        };
+        tile_cursor.move_by(direction(x), direction(y));
+        *cooldown = STICK_REPEAT_SECONDS;
+        return;
+    }
+
+    // Stick released, the next push moves immediately
+    *cooldown = 0.0;
+}
+
+/// System to hand placement back to the mouse as soon as it moves
+fn clear_tile_cursor_on_mouse_move(
+    mut cursor_moved: EventReader<CursorMoved>,
+    mut tile_cursor: ResMut<TileCursor>,
+) {
+    if cursor_moved.read().count() > 0 && tile_cursor.tile.is_some() {
+        tile_cursor.tile = None;
+    }
+}
+
+/// System to highlight the tile selected by the [`TileCursor`]
+fn render_tile_cursor(
+    tile_cursor: Res<TileCursor>,
+    mut query: Query<(&mut Transform, &mut Visibility), With<TileCursorMarker>>,
+) {
+    if !tile_cursor.is_changed() {
+        return;
+    }
+
+    for (mut transform, mut visibility) in query.iter_mut() {
+        match &tile_cursor.tile {
+            Some(tile) => {
+                let coordinates: ActuallyLogicalCoordinates = tile.clone().into();
+                transform.translation = coordinates.transform.translation;
+                transform.translation.z = TILE_CURSOR_Z;
+                *visibility = Visibility::Visible;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
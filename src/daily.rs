@@ -0,0 +1,224 @@
+use std::{error::Error, fs, path::Path};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use chrono::{Datelike, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    board::{
+        bundle::BoardLimit,
+        win_condition::{GameFinished, GameResult},
+    },
+    bot::{
+        plugin::BotSeats,
+        strategy::{BotKind, BotSetup},
+    },
+    card::{deck::DeckDefinition, sequence::GameSeed, spawn::CardIndex},
+    cli::Cli,
+    hint::HintsUsed,
+    keys::keymap::{Action, ActionInput},
+    replay::viewer::ReplayViewer,
+    schedule::InGameSet,
+    state::{can_start_game, AppState},
+};
+
+/// Bot every opponent seat is played by during the daily challenge
+pub const DAILY_BOT: BotKind = BotKind::Greedy;
+/// File the results of finished daily challenges are written to, relative to the working directory
+pub const DAILY_HISTORY_FILE: &str = "daily_history.ron";
+
+/// Present while playing the daily challenge, everyone gets the same cards on the same day. It is
+/// always dealt from the built-in [`DeckDefinition`] without a [`BoardLimit`]
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct DailyChallenge {
+    pub date: NaiveDate,
+}
+
+impl DailyChallenge {
+    pub fn today() -> Self {
+        Self {
+            date: Local::now().date_naive(),
+        }
+    }
+
+    /// A person in the first seat, every other seat is played by the [`DAILY_BOT`]
+    pub fn bot_seats(num_players: usize) -> BotSeats {
+        BotSeats::opponents(num_players, &vec![BotSetup::new(DAILY_BOT); num_players])
+    }
+
+    /// [`GameSeed`] derived from the date, e.g. `20240131`
+    pub fn seed(&self) -> GameSeed {
+        GameSeed(
+            self.date.year() as u64 * 10_000
+                + self.date.month() as u64 * 100
+                + self.date.day() as u64,
+        )
+    }
+}
+
+/// Seats, deck and board limit from before the [`DailyChallenge`] was started, taken again once
+/// it is left
+#[derive(Resource)]
+pub struct BeforeDaily {
+    pub seats: BotSeats,
+    pub deck: DeckDefinition,
+    pub board_limit: BoardLimit,
+}
+
+/// Group of all system parameters used to leave the [`DailyChallenge`]
+#[derive(SystemParam)]
+pub struct LeaveDailyContext<'w> {
+    pub challenge: Option<Res<'w, DailyChallenge>>,
+    pub before: Option<ResMut<'w, BeforeDaily>>,
+    pub deck: ResMut<'w, DeckDefinition>,
+    pub board_limit: ResMut<'w, BoardLimit>,
+    pub cli: Res<'w, Cli>,
+}
+
+impl LeaveDailyContext<'_> {
+    /// Stop playing the [`DailyChallenge`], if it is played, and take the seats, deck and board
+    /// limit from before it again. A challenge started with `--daily` or `--load` goes back to the
+    /// command line instead
+    pub fn leave(&mut self, commands: &mut Commands) {
+        if self.challenge.is_none() {
+            return;
+        }
+        commands.remove_resource::<DailyChallenge>();
+        commands.remove_resource::<BeforeDaily>();
+        let bot_seats = match self.before.as_mut() {
+            Some(before) => {
+                *self.deck = before.deck.clone();
+                *self.board_limit = before.board_limit;
+                std::mem::take(&mut before.seats)
+            }
+            None => {
+                *self.deck = self.cli.deck_definition();
+                *self.board_limit = self.cli.board_limit();
+                BotSeats::opponents(self.deck.num_players(), &self.cli.bot_setups())
+            }
+        };
+        commands.insert_resource(bot_seats);
+    }
+}
+
+/// Result of a single finished daily challenge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyResult {
+    pub date: NaiveDate,
+    pub seed: u64,
+    pub result: GameResult,
+    /// Number of cards placed before the game finished
+    pub moves: usize,
+    /// Number of hints asked for
+    #[serde(default)]
+    pub hints: usize,
+}
+
+/// All finished daily challenges, oldest first
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DailyHistory(pub Vec<DailyResult>);
+
+impl DailyHistory {
+    /// Load the history, an absent file is an empty history
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path)?;
+        Ok(ron::from_str(&contents)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+pub struct DailyPlugin;
+
+impl Plugin for DailyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            start_daily
+                .run_if(can_start_game)
+                .in_set(InGameSet::MutateBoard),
+        );
+        app.add_systems(
+            Update,
+            record_daily_result
+                .run_if(resource_exists::<DailyChallenge>)
+                .run_if(not(resource_exists::<ReplayViewer>))
+                .in_set(InGameSet::LogState),
+        );
+    }
+}
+
+/// System to start today's [`DailyChallenge`], it is dealt on leaving the player setup
+fn start_daily(
+    mut commands: Commands,
+    input: ActionInput,
+    mut deck: ResMut<DeckDefinition>,
+    mut board_limit: ResMut<BoardLimit>,
+    mut bot_seats: ResMut<BotSeats>,
+    playing: Option<Res<DailyChallenge>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if input.just_pressed(Action::Daily) {
+        let challenge = DailyChallenge::today();
+        info!("Starting daily challenge of {}", challenge.date);
+
+        if playing.is_none() {
+            commands.insert_resource(BeforeDaily {
+                seats: std::mem::take(&mut *bot_seats),
+                deck: deck.clone(),
+                board_limit: *board_limit,
+            });
+        }
+        *deck = DeckDefinition::default();
+        *board_limit = BoardLimit::default();
+        commands.insert_resource(challenge);
+        commands.insert_resource(DailyChallenge::bot_seats(deck.num_players()));
+        next_state.set(AppState::PlayerSetup);
+    }
+}
+
+/// System to write the result of a finished [`DailyChallenge`] to the [`DailyHistory`], only the
+/// first finish of each day counts
+fn record_daily_result(
+    mut game_finished: EventReader<GameFinished>,
+    challenge: Res<DailyChallenge>,
+    card_index: Res<CardIndex>,
+    hints_used: Res<HintsUsed>,
+) {
+    for GameFinished(result) in game_finished.read() {
+        let path = Path::new(DAILY_HISTORY_FILE);
+        let mut history = match DailyHistory::load(path) {
+            Ok(history) => history,
+            Err(err) => {
+                error!("Failed to load daily history {:?}: {}", path, err);
+                return;
+            }
+        };
+
+        if history.0.iter().any(|entry| entry.date == challenge.date) {
+            info!("Daily challenge of {} was already recorded", challenge.date);
+            continue;
+        }
+
+        let entry = DailyResult {
+            date: challenge.date,
+            seed: challenge.seed().0,
+            result: *result,
+            moves: card_index.index,
+            hints: hints_used.0,
+        };
+        info!("Daily challenge result: {:?}", entry);
+        history.0.push(entry);
+
+        if let Err(err) = history.save(path) {
+            error!("Failed to save daily history {:?}: {}", path, err);
+        }
+    }
+}
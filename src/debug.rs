@@ -5,7 +5,7 @@ use bevy::{
 };
 use std::any::type_name;
 
-use crate::{board::debug::log_gamestate, keys::print_keymap, schedule::InGameSet};
+use crate::{board::debug::log_gamestate, schedule::InGameSet};
 
 const LOG_PERIOD: f32 = 1.0;
 
@@ -36,7 +36,6 @@ impl Plugin for DebugPlugin {
                 // log_entity_position::<TextMarker>,
                 log_window_dimensions_on_resize,
                 log_gamestate,
-                print_keymap,
             )
                 .chain()
                 .in_set(InGameSet::LogState),
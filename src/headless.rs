@@ -0,0 +1,50 @@
+use bevy::{
+    log::{Level, LogPlugin},
+    prelude::*,
+};
+
+use crate::{
+    asset_loader::AssetStore,
+    board::{
+        bundle::{setup_board, BoardLimit},
+        win_condition::{GameFinished, PlayerWinEntity},
+    },
+    card::{sequence::generate_card_sequences, spawn::CardIndex, undo::CardHistory},
+    replay::resume::resume_saved_game,
+};
+
+/// Replaces the windowed plugins with the bare game logic, the app updates as fast as it can.
+/// Used for scripted runs, CI smoke tests and simulations
+pub struct HeadlessPlugin {
+    pub log_level: Level,
+}
+
+impl Default for HeadlessPlugin {
+    fn default() -> Self {
+        Self {
+            log_level: Level::INFO,
+        }
+    }
+}
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            MinimalPlugins,
+            LogPlugin {
+                level: self.log_level,
+                ..default()
+            },
+        ));
+        app.init_resource::<AssetStore>();
+        app.init_resource::<BoardLimit>();
+        app.insert_resource(CardIndex::default());
+        app.insert_resource(CardHistory(None));
+        app.insert_resource(PlayerWinEntity(None));
+        app.add_event::<GameFinished>();
+        app.add_systems(
+            Startup,
+            (setup_board, generate_card_sequences, resume_saved_game).chain(),
+        );
+    }
+}
@@ -0,0 +1,272 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::*,
+    tasks::{block_on, poll_once, AsyncComputeTaskPool, Task},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    asset_loader::AssetStore,
+    board::{
+        bundle::{BoardLimit, GameState, TILE_SIZE},
+        win_condition::{longest_line, winning_line, CARDS_TO_WIN},
+    },
+    bot::{
+        mcts::MctsBot,
+        plugin::BotSeats,
+        strategy::{Bot, BotPosition, BotView, SearchBudget},
+    },
+    card::{
+        bundle::CardColor,
+        deck::DeckDefinition,
+        sequence::{CardSequence, GameSeed},
+        spawn::{get_next_card, CardIndex},
+    },
+    coordinates::{ActuallyLogicalCoordinates, BoardCoordinates},
+    keys::keymap::{Action, ActionInput},
+    schedule::InGameSet,
+    state::AppState,
+};
+
+/// Z at which the hint is rendered, above the tile cursor
+const HINT_Z: f32 = 60.0;
+const HINT_COLOR: Color = Color::rgba(1.0, 1.0, 0.6, 0.0);
+/// Lowest and highest alpha of the pulsing hint highlight
+const HINT_ALPHA_RANGE: (f32, f32) = (0.15, 0.6);
+/// Pulses per second of the hint highlight
+const HINT_PULSE_SPEED: f32 = 1.5;
+const HINT_TEXT_FONT_SIZE: f32 = 28.0;
+/// The hint is searched off the main thread, short enough to show up right after asking
+const HINT_BUDGET: SearchBudget = SearchBudget {
+    playouts: 300,
+    time_limit: Duration::from_millis(150),
+};
+
+/// Number of hints asked for during the current game
+#[derive(Resource, Debug, Default)]
+pub struct HintsUsed(pub usize);
+
+/// Marker component for the hint highlight and its explanation
+#[derive(Component)]
+pub struct HintMarker;
+
+pub struct HintPlugin;
+
+impl Plugin for HintPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HintsUsed>();
+        app.add_systems(
+            Update,
+            show_hint
+                .run_if(in_state(AppState::InGame))
+                .in_set(InGameSet::MutateBoard),
+        );
+        app.add_systems(
+            Update,
+            (clear_hint, pulse_hint).chain().in_set(InGameSet::LogState),
+        );
+    }
+}
+
+/// Group of all system parameters used to suggest a tile
+#[derive(SystemParam)]
+pub struct HintContext<'w> {
+    pub board_state: Res<'w, GameState>,
+    pub card_index: Res<'w, CardIndex>,
+    pub card_sequence: Res<'w, CardSequence>,
+    pub board_limit: Res<'w, BoardLimit>,
+    pub deck: Res<'w, DeckDefinition>,
+    pub game_seed: Res<'w, GameSeed>,
+    pub bot_seats: Res<'w, BotSeats>,
+    pub asset_store: Res<'w, AssetStore>,
+    pub hints_used: ResMut<'w, HintsUsed>,
+}
+
+/// A hint being searched on the [`AsyncComputeTaskPool`], the tile with its explanation
+pub struct PendingHint {
+    task: Task<Option<(BoardCoordinates, String)>>,
+}
+
+impl PendingHint {
+    /// Start searching on a copy of the position. Its own generator, so asking for a hint does
+    /// not change what the bots play. Seeded from the game and the card, the same position always
+    /// gets the same hint
+    fn spawn(view: &BotView, game_seed: GameSeed) -> Self {
+        let position = BotPosition::new(view);
+        let mut rng = StdRng::seed_from_u64(game_seed.0.wrapping_add(view.played.len() as u64));
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            let view = position.view();
+            let tile = MctsBot.choose_move(&view, &mut rng)?;
+            let explanation = explain(&view, &tile);
+            Some((tile, explanation))
+        });
+        Self { task }
+    }
+}
+
+/// System to search the best tile for the next card on the HUD and highlight it once found, with
+/// a short explanation why it is good. A search is dropped if the board changes in the meantime
+fn show_hint(
+    mut commands: Commands,
+    input: ActionInput,
+    mut pending: Local<Option<PendingHint>>,
+    mut context: HintContext,
+    hints: Query<Entity, With<HintMarker>>,
+) {
+    if context.board_state.is_changed() {
+        *pending = None;
+    }
+
+    if let Some(pending_hint) = pending.as_mut() {
+        let Some(hint) = block_on(poll_once(&mut pending_hint.task)) else {
+            return;
+        };
+        *pending = None;
+        let Some((tile, explanation)) = hint else {
+            info!("No tile to hint, the card fits nowhere");
+            return;
+        };
+        info!("Hint: place the card at {:?}, {}", tile, explanation);
+
+        for entity in hints.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        context.hints_used.0 += 1;
+        spawn_hint(&mut commands, tile, explanation, &context.asset_store);
+        return;
+    }
+
+    if !input.just_pressed(Action::Hint) {
+        return;
+    }
+    let index = context.card_index.index;
+    if context.bot_seats.is_bot_turn(index) || winning_line(&context.board_state).is_some() {
+        return;
+    }
+    let Some(card) = get_next_card(&context.card_index, &context.card_sequence) else {
+        return;
+    };
+
+    let players = context.deck.players();
+    let view = BotView {
+        board_state: &context.board_state,
+        card,
+        board_limit: *context.board_limit,
+        seat: context.bot_seats.seat_for_turn(index),
+        players: &players,
+        deck: &context.deck,
+        played: &context.card_sequence.cards[..index],
+        budget: HINT_BUDGET,
+    };
+    *pending = Some(PendingHint::spawn(&view, *context.game_seed));
+}
+
+/// Why placing the card on this tile is good, e.g. "blocks yellow's 3-line"
+pub fn explain(view: &BotView, coordinates: &BoardCoordinates) -> String {
+    let (x, y, _) = coordinates.as_xys();
+    let cards_to_win = CARDS_TO_WIN as usize;
+
+    let own = (!view.card.color.is_neutral())
+        .then(|| longest_line(view.board_state, x, y, view.card.color));
+    let threat = view
+        .opponent_colors()
+        .map(|color| (color, longest_line(view.board_state, x, y, color).0))
+        .max_by_key(|(_, length)| *length);
+    let threat_length = threat.map_or(0, |(_, length)| length);
+
+    match (own, threat) {
+        (Some((length, direction)), _) if length >= cards_to_win => format!(
+            "completes your {} {}",
+            color_name(view.card.color),
+            direction_name(direction)
+        ),
+        (_, Some((color, length))) if length >= cards_to_win => {
+            format!("blocks {}'s {}-line", color_name(color), length - 1)
+        }
+        (Some((length, direction)), _) if length > 1 && length >= threat_length => format!(
+            "extends your {} {} to {}",
+            color_name(view.card.color),
+            direction_name(direction),
+            length
+        ),
+        (_, Some((color, length))) if length > 1 => {
+            format!("blocks {}'s {}-line", color_name(color), length - 1)
+        }
+        _ => "keeps your options open".to_string(),
+    }
+}
+
+fn color_name(color: CardColor) -> String {
+    format!("{:?}", color).to_lowercase()
+}
+
+/// Name of a direction out of [`LINE_DIRECTIONS`](crate::board::win_condition::LINE_DIRECTIONS)
+fn direction_name(direction: (i32, i32)) -> &'static str {
+    match direction {
+        (_, 0) => "row",
+        (0, _) => "column",
+        _ => "diagonal",
+    }
+}
+
+fn spawn_hint(
+    commands: &mut Commands,
+    tile: BoardCoordinates,
+    explanation: String,
+    asset_store: &AssetStore,
+) {
+    let mut coordinates: ActuallyLogicalCoordinates = tile.into();
+    coordinates.transform.translation.z = HINT_Z;
+
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: HINT_COLOR,
+                    custom_size: Some(Vec2::splat(TILE_SIZE)),
+                    ..default()
+                },
+                transform: coordinates.transform,
+                ..default()
+            },
+            HintMarker,
+        ))
+        .with_children(|parent| {
+            parent.spawn(Text2dBundle {
+                text: Text::from_section(
+                    explanation,
+                    TextStyle {
+                        font_size: HINT_TEXT_FONT_SIZE,
+                        color: Color::WHITE,
+                        font: asset_store.font.clone(),
+                    },
+                ),
+                transform: Transform::from_xyz(0.0, TILE_SIZE * 0.75, 1.0),
+                ..default()
+            });
+        });
+}
+
+/// System to fade the hint highlight in and out
+fn pulse_hint(time: Res<Time>, mut query: Query<&mut Sprite, With<HintMarker>>) {
+    let (min, max) = HINT_ALPHA_RANGE;
+    let wave = (time.elapsed_seconds() * HINT_PULSE_SPEED * std::f32::consts::TAU).sin();
+    for mut sprite in query.iter_mut() {
+        sprite.color.set_a(min + (max - min) * (wave + 1.0) / 2.0);
+    }
+}
+
+/// System to remove the hint as soon as a card is placed, undone or the game restarts
+fn clear_hint(
+    mut commands: Commands,
+    board_state: Res<GameState>,
+    hints: Query<Entity, With<HintMarker>>,
+) {
+    if board_state.is_changed() {
+        for entity in hints.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    asset_loader::AssetStore,
+    bot::plugin::BotSeats,
+    card::{
+        deck::DeckDefinition,
+        sequence::{CardSequence, GameSeed},
+        spawn::CardIndex,
+    },
+    profile::plugin::SeatProfiles,
+    schedule::InGameSet,
+    state::AppState,
+    tourney::{bracket::Tourney, standings::match_score},
+};
+
+const HUD_FONT_SIZE: f32 = 24.0;
+const HUD_CARD_FONT_SIZE: f32 = 40.0;
+const HUD_CARD_SIZE: f32 = 70.0;
+const HUD_MARGIN: f32 = 10.0;
+const HUD_PADDING: f32 = 12.0;
+const HUD_GAP: f32 = 6.0;
+const HUD_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.6);
+const HUD_TEXT_COLOR: Color = Color::WHITE;
+const HUD_CARD_TEXT_COLOR: Color = Color::BLACK;
+
+/// Marker component for the root node of the HUD, shown in the top right corner whenever the board
+/// is on screen
+#[derive(Component)]
+pub struct Hud;
+
+/// The part of the [`Hud`] a text shows, its value is replaced in place whenever the game changes
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HudText {
+    /// Value of the next card in the [`CardSequence`]
+    NextCard,
+    /// Whose turn it is
+    CurrentPlayer,
+    /// Cards left per seat
+    DeckCounts,
+    /// Score of the current [`Tourney`] match, empty outside of a tourney
+    MatchScore,
+    Seed,
+}
+
+/// Marker component for the node drawn in the color of the next card
+#[derive(Component)]
+pub struct HudNextCard;
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostStartup, spawn_hud);
+        app.add_systems(
+            Update,
+            (
+                show_hud.run_if(state_changed::<AppState>),
+                update_hud.run_if(board_changed),
+            )
+                .in_set(InGameSet::LogState),
+        );
+    }
+}
+
+/// Run condition for redrawing the [`Hud`], whenever a card is placed or taken back, a new game is
+/// dealt or the players change
+fn board_changed(
+    card_index: Res<CardIndex>,
+    card_sequence: Res<CardSequence>,
+    deck: Res<DeckDefinition>,
+    seat_profiles: Res<SeatProfiles>,
+    bot_seats: Res<BotSeats>,
+    tourney: Option<Res<Tourney>>,
+) -> bool {
+    card_index.is_changed()
+        || card_sequence.is_changed()
+        || deck.is_changed()
+        || seat_profiles.is_changed()
+        || bot_seats.is_changed()
+        || tourney.is_some_and(|tourney| tourney.is_changed())
+}
+
+/// System to spawn the [`Hud`] once, it is only hidden and updated from then on
+fn spawn_hud(mut commands: Commands, asset_store: Res<AssetStore>) {
+    let text = |part: HudText, font_size: f32, color: Color| {
+        (
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: asset_store.font.clone(),
+                    font_size,
+                    color,
+                },
+            ),
+            part,
+        )
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(HUD_MARGIN),
+                    right: Val::Px(HUD_MARGIN),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::End,
+                    padding: UiRect::all(Val::Px(HUD_PADDING)),
+                    row_gap: Val::Px(HUD_GAP),
+                    ..default()
+                },
+                background_color: HUD_BACKGROUND_COLOR.into(),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            Hud,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Next card",
+                TextStyle {
+                    font: asset_store.font.clone(),
+                    font_size: HUD_FONT_SIZE,
+                    color: HUD_TEXT_COLOR,
+                },
+            ));
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Px(HUD_CARD_SIZE),
+                            height: Val::Px(HUD_CARD_SIZE),
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    HudNextCard,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(text(
+                        HudText::NextCard,
+                        HUD_CARD_FONT_SIZE,
+                        HUD_CARD_TEXT_COLOR,
+                    ));
+                });
+            for part in [
+                HudText::CurrentPlayer,
+                HudText::DeckCounts,
+                HudText::MatchScore,
+                HudText::Seed,
+            ] {
+                parent.spawn(text(part, HUD_FONT_SIZE, HUD_TEXT_COLOR));
+            }
+        });
+}
+
+/// System to show the [`Hud`] only while the board is on screen
+fn show_hud(state: Res<State<AppState>>, mut query: Query<&mut Visibility, With<Hud>>) {
+    for mut visibility in query.iter_mut() {
+        *visibility = if state.shows_board() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+/// Group of all system parameters shown on the [`Hud`]
+#[derive(SystemParam)]
+pub struct HudContext<'w> {
+    pub card_index: Res<'w, CardIndex>,
+    pub card_sequence: Res<'w, CardSequence>,
+    pub game_seed: Res<'w, GameSeed>,
+    pub deck: Res<'w, DeckDefinition>,
+    pub seat_profiles: Res<'w, SeatProfiles>,
+    pub bot_seats: Res<'w, BotSeats>,
+    pub tourney: Option<Res<'w, Tourney>>,
+}
+
+impl HudContext<'_> {
+    /// Profile name of the seat, or its colors, e.g. `alice` or `Red/Yellow`
+    pub fn seat_name(&self, seat: usize) -> String {
+        match self.seat_profiles.name(seat) {
+            Some(name) => name.to_string(),
+            None => self.deck.players()[seat]
+                .iter()
+                .map(|color| format!("{:?}", color))
+                .collect::<Vec<_>>()
+                .join("/"),
+        }
+    }
+
+    /// Text of the given part of the [`Hud`]
+    pub fn text(&self, part: HudText) -> String {
+        let num_players = self.deck.num_players();
+        match part {
+            HudText::NextCard => self
+                .card_sequence
+                .cards
+                .get(self.card_index.index)
+                .map(|card| card.value.to_string())
+                .unwrap_or_default(),
+            HudText::CurrentPlayer => {
+                if self.card_index.index >= self.card_sequence.cards.len() {
+                    return "No cards left".to_string();
+                }
+                let seat = self.card_index.index % num_players;
+                let bot = if self.bot_seats.is_bot_turn(self.card_index.index) {
+                    " (bot)"
+                } else {
+                    ""
+                };
+                format!("{} to play{}", self.seat_name(seat), bot)
+            }
+            HudText::DeckCounts => self
+                .card_sequence
+                .cards_left(self.card_index.index, num_players)
+                .into_iter()
+                .enumerate()
+                .map(|(seat, cards_left)| format!("{}: {} left", self.seat_name(seat), cards_left))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            HudText::MatchScore => self
+                .tourney
+                .as_ref()
+                .map(|tourney| match_score(tourney, &self.deck))
+                .unwrap_or_default(),
+            HudText::Seed => format!("Seed {}", self.game_seed.0),
+        }
+    }
+}
+
+/// System to update the [`Hud`] in place, so players know what card they are about to place
+fn update_hud(
+    context: HudContext,
+    mut texts: Query<(&HudText, &mut Text)>,
+    mut next_card: Query<&mut BackgroundColor, With<HudNextCard>>,
+) {
+    for (part, mut text) in texts.iter_mut() {
+        text.sections[0].value = context.text(*part);
+    }
+
+    let color = context
+        .card_sequence
+        .cards
+        .get(context.card_index.index)
+        .map_or(Color::NONE, |card| card.color.to_color());
+    for mut background in next_card.iter_mut() {
+        background.0 = color;
+    }
+}
@@ -0,0 +1,337 @@
+use std::{collections::BTreeMap, error::Error, fmt, fs, path::Path};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{profile::stats::StatsScreen, settings::options::OptionsScreen};
+
+use super::rebind::RebindScreen;
+
+/// File the [`KeyMap`] is persisted to, relative to the working directory
+pub const KEYMAP_FILE: &str = "keymap.ron";
+
+/// Everything a player can do through an input binding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Action {
+    Quit,
+    Restart,
+    Undo,
+    Spawn,
+    Hint,
+    Daily,
+    Replay,
+    ReplayForward,
+    ReplayBack,
+    ReplayStart,
+    ReplayEnd,
+    ReplayAutoplay,
+    ReplayFaster,
+    ReplaySlower,
+    DragCamera,
+    ZoomIn,
+    ZoomOut,
+    CursorUp,
+    CursorDown,
+    CursorLeft,
+    CursorRight,
+    Rebind,
+    Options,
+    Stats,
+}
+
+impl Action {
+    pub const ALL: [Action; 24] = [
+        Action::Quit,
+        Action::Restart,
+        Action::Undo,
+        Action::Spawn,
+        Action::Hint,
+        Action::Daily,
+        Action::Replay,
+        Action::ReplayForward,
+        Action::ReplayBack,
+        Action::ReplayStart,
+        Action::ReplayEnd,
+        Action::ReplayAutoplay,
+        Action::ReplayFaster,
+        Action::ReplaySlower,
+        Action::DragCamera,
+        Action::ZoomIn,
+        Action::ZoomOut,
+        Action::CursorUp,
+        Action::CursorDown,
+        Action::CursorLeft,
+        Action::CursorRight,
+        Action::Rebind,
+        Action::Options,
+        Action::Stats,
+    ];
+}
+
+/// A single input an [`Action`] can be bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButtonType),
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Binding::Key(key) => write!(f, "{:?}", key),
+            Binding::Mouse(button) => write!(f, "Mouse {:?}", button),
+            Binding::Gamepad(button) => write!(f, "Gamepad {:?}", button),
+        }
+    }
+}
+
+/// Maps every [`Action`] to the [`Binding`]s that trigger it
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyMap(pub BTreeMap<Action, Vec<Binding>>);
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        use Binding::{Gamepad, Key, Mouse};
+
+        let mut keymap = BTreeMap::new();
+        keymap.insert(Action::Quit, vec![Key(KeyCode::Escape)]);
+        keymap.insert(Action::Restart, vec![Key(KeyCode::Enter)]);
+        keymap.insert(
+            Action::Undo,
+            vec![Key(KeyCode::KeyU), Gamepad(GamepadButtonType::East)],
+        );
+        keymap.insert(
+            Action::Spawn,
+            vec![Key(KeyCode::Space), Gamepad(GamepadButtonType::South)],
+        );
+        keymap.insert(
+            Action::Hint,
+            vec![Key(KeyCode::KeyH), Gamepad(GamepadButtonType::North)],
+        );
+        keymap.insert(Action::Daily, vec![Key(KeyCode::KeyC)]);
+        keymap.insert(Action::Replay, vec![Key(KeyCode::KeyR)]);
+        keymap.insert(Action::ReplayForward, vec![Key(KeyCode::ArrowRight)]);
+        keymap.insert(Action::ReplayBack, vec![Key(KeyCode::ArrowLeft)]);
+        keymap.insert(Action::ReplayStart, vec![Key(KeyCode::Home)]);
+        keymap.insert(Action::ReplayEnd, vec![Key(KeyCode::End)]);
+        keymap.insert(Action::ReplayAutoplay, vec![Key(KeyCode::KeyP)]);
+        keymap.insert(Action::ReplayFaster, vec![Key(KeyCode::ArrowUp)]);
+        keymap.insert(Action::ReplaySlower, vec![Key(KeyCode::ArrowDown)]);
+        keymap.insert(Action::DragCamera, vec![Mouse(MouseButton::Left)]);
+        keymap.insert(
+            Action::ZoomIn,
+            vec![
+                Key(KeyCode::Equal),
+                Gamepad(GamepadButtonType::RightTrigger),
+            ],
+        );
+        keymap.insert(
+            Action::ZoomOut,
+            vec![Key(KeyCode::Minus), Gamepad(GamepadButtonType::LeftTrigger)],
+        );
+        keymap.insert(
+            Action::CursorUp,
+            vec![
+                Key(KeyCode::KeyW),
+                Key(KeyCode::ArrowUp),
+                Gamepad(GamepadButtonType::DPadUp),
+            ],
+        );
+        keymap.insert(
+            Action::CursorDown,
+            vec![
+                Key(KeyCode::KeyS),
+                Key(KeyCode::ArrowDown),
+                Gamepad(GamepadButtonType::DPadDown),
+            ],
+        );
+        keymap.insert(
+            Action::CursorLeft,
+            vec![
+                Key(KeyCode::KeyA),
+                Key(KeyCode::ArrowLeft),
+                Gamepad(GamepadButtonType::DPadLeft),
+            ],
+        );
+        keymap.insert(
+            Action::CursorRight,
+            vec![
+                Key(KeyCode::KeyD),
+                Key(KeyCode::ArrowRight),
+                Gamepad(GamepadButtonType::DPadRight),
+            ],
+        );
+        keymap.insert(Action::Rebind, vec![Key(KeyCode::F1)]);
+        keymap.insert(Action::Options, vec![Key(KeyCode::F2)]);
+        keymap.insert(Action::Stats, vec![Key(KeyCode::F3)]);
+        KeyMap(keymap)
+    }
+}
+
+impl KeyMap {
+    pub fn bindings(&self, action: Action) -> &[Binding] {
+        self.0.get(&action).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Add a [`Binding`] to an [`Action`], ignoring duplicates
+    pub fn bind(&mut self, action: Action, binding: Binding) {
+        let bindings = self.0.entry(action).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    pub fn clear(&mut self, action: Action) {
+        self.0.entry(action).or_default().clear();
+    }
+
+    /// Load a [`KeyMap`], [`Action`]s missing from the file keep their default [`Binding`]s
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+        let loaded: KeyMap = ron::from_str(&contents)?;
+        Ok(KeyMap::with_defaults(loaded))
+    }
+
+    /// Fill in the default [`Binding`]s of [`Action`]s missing from a saved [`KeyMap`]. A file
+    /// written before an action existed may already use its default binding for another action,
+    /// e.g. `D` for [`Action::Daily`] and [`Action::CursorRight`], the saved binding wins then
+    fn with_defaults(saved: KeyMap) -> Self {
+        let mut keymap = KeyMap::default();
+        for (action, bindings) in keymap.0.iter_mut() {
+            if saved.0.contains_key(action) {
+                continue;
+            }
+            bindings.retain(|binding| {
+                let taken_by = saved
+                    .0
+                    .iter()
+                    .find(|(_, saved_bindings)| saved_bindings.contains(binding));
+                if let Some((other, _)) = taken_by {
+                    warn!(
+                        "{} is bound to {:?} in the keymap, not binding it to {:?} too",
+                        binding, other, action
+                    );
+                }
+                taken_by.is_none()
+            });
+        }
+        keymap.0.extend(saved.0);
+        keymap
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Load the [`KEYMAP_FILE`], or the default [`KeyMap`] if there is none
+    pub fn load_or_default() -> Self {
+        let path = Path::new(KEYMAP_FILE);
+        if !path.exists() {
+            return KeyMap::default();
+        }
+
+        match KeyMap::load(path) {
+            Ok(keymap) => keymap,
+            Err(err) => {
+                warn!(
+                    "Failed to load keymap {:?}, using the default: {}",
+                    path, err
+                );
+                KeyMap::default()
+            }
+        }
+    }
+}
+
+/// Group of all system parameters used to check if an [`Action`] was triggered
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    pub keymap: Res<'w, KeyMap>,
+    pub keyboard: Res<'w, ButtonInput<KeyCode>>,
+    pub mouse: Res<'w, ButtonInput<MouseButton>>,
+    pub gamepad_buttons: Res<'w, ButtonInput<GamepadButton>>,
+    pub gamepads: Res<'w, Gamepads>,
+    /// Actions are not triggered while they are being rebound
+    pub rebind_screen: Option<Res<'w, RebindScreen>>,
+    /// Or while the options screen is open
+    pub options_screen: Option<Res<'w, OptionsScreen>>,
+    /// Or while the profile stats are shown
+    pub stats_screen: Option<Res<'w, StatsScreen>>,
+}
+
+impl ActionInput<'_> {
+    /// Was any [`Binding`] of this [`Action`] pressed this frame?
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.any_binding(action, |binding| match binding {
+            Binding::Key(key) => self.keyboard.just_pressed(key),
+            Binding::Mouse(button) => self.mouse.just_pressed(button),
+            Binding::Gamepad(button) => self.gamepads.iter().any(|gamepad| {
+                self.gamepad_buttons
+                    .just_pressed(GamepadButton::new(gamepad, button))
+            }),
+        })
+    }
+
+    /// Is any [`Binding`] of this [`Action`] being held down?
+    pub fn pressed(&self, action: Action) -> bool {
+        self.any_binding(action, |binding| match binding {
+            Binding::Key(key) => self.keyboard.pressed(key),
+            Binding::Mouse(button) => self.mouse.pressed(button),
+            Binding::Gamepad(button) => self.gamepads.iter().any(|gamepad| {
+                self.gamepad_buttons
+                    .pressed(GamepadButton::new(gamepad, button))
+            }),
+        })
+    }
+
+    /// Is one of the overlay screens open and taking all input?
+    pub fn is_blocked(&self) -> bool {
+        self.rebind_screen.is_some() || self.options_screen.is_some() || self.stats_screen.is_some()
+    }
+
+    fn any_binding(&self, action: Action, is_active: impl Fn(Binding) -> bool) -> bool {
+        if self.is_blocked() {
+            return false;
+        }
+        self.keymap
+            .bindings(action)
+            .iter()
+            .any(|binding| is_active(*binding))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_bindings_win_over_new_defaults() {
+        let saved = KeyMap(BTreeMap::from([(
+            Action::Daily,
+            vec![Binding::Key(KeyCode::KeyD)],
+        )]));
+        let keymap = KeyMap::with_defaults(saved);
+
+        assert_eq!(
+            keymap.bindings(Action::Daily),
+            &[Binding::Key(KeyCode::KeyD)]
+        );
+        assert!(!keymap
+            .bindings(Action::CursorRight)
+            .contains(&Binding::Key(KeyCode::KeyD)));
+        assert!(keymap
+            .bindings(Action::CursorRight)
+            .contains(&Binding::Key(KeyCode::ArrowRight)));
+    }
+
+    #[test]
+    fn missing_actions_keep_their_defaults() {
+        assert_eq!(
+            KeyMap::with_defaults(KeyMap(BTreeMap::new())),
+            KeyMap::default()
+        );
+    }
+}
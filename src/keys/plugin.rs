@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use crate::schedule::InGameSet;
+
+use super::{
+    keymap::KeyMap,
+    rebind::{navigate_rebind_screen, open_rebind_screen, render_rebind_screen, RebindScreen},
+};
+
+pub struct KeysPlugin;
+
+impl Plugin for KeysPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(KeyMap::load_or_default());
+        app.add_systems(
+            Update,
+            (
+                open_rebind_screen.run_if(not(resource_exists::<RebindScreen>)),
+                (navigate_rebind_screen, render_rebind_screen)
+                    .chain()
+                    .run_if(resource_exists::<RebindScreen>),
+            )
+                .chain()
+                .in_set(InGameSet::HandleMenu),
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+
+use crate::asset_loader::AssetStore;
+
+use super::keymap::{Action, ActionInput, Binding, KeyMap, KEYMAP_FILE};
+
+const REBIND_FONT_SIZE: f32 = 30.0;
+const REBIND_PADDING: f32 = 40.0;
+const REBIND_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.85);
+const REBIND_TEXT_COLOR: Color = Color::WHITE;
+const REBIND_SELECTED_COLOR: Color = Color::GOLD;
+
+/// Present while the rebinding screen is open, regular [`Action`]s are not triggered meanwhile
+#[derive(Resource, Default)]
+pub struct RebindScreen {
+    /// Index into [`Action::ALL`] of the selected [`Action`]
+    pub selected: usize,
+    /// Waiting for the next pressed input to bind to the selected [`Action`]
+    pub capturing: bool,
+}
+
+/// Marker component for the root node of the rebinding screen
+#[derive(Component)]
+pub struct RebindScreenMarker;
+
+/// System to open the rebinding screen
+pub fn open_rebind_screen(mut commands: Commands, input: ActionInput) {
+    if input.just_pressed(Action::Rebind) {
+        commands.insert_resource(RebindScreen::default());
+    }
+}
+
+/// System to navigate the rebinding screen and capture new [`Binding`]s.
+/// Uses fixed keys so a broken [`KeyMap`] can always be repaired: arrows to select, enter to add a
+/// binding, delete to clear the bindings and escape to close
+pub fn navigate_rebind_screen(
+    mut commands: Commands,
+    mut screen: ResMut<RebindScreen>,
+    mut keymap: ResMut<KeyMap>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    query: Query<Entity, With<RebindScreenMarker>>,
+) {
+    let action = Action::ALL[screen.selected];
+
+    if screen.capturing {
+        if keyboard.just_pressed(KeyCode::Escape) {
+            screen.capturing = false;
+            return;
+        }
+
+        let binding = keyboard
+            .get_just_pressed()
+            .next()
+            .map(|key| Binding::Key(*key))
+            .or_else(|| {
+                mouse
+                    .get_just_pressed()
+                    .next()
+                    .map(|button| Binding::Mouse(*button))
+            })
+            .or_else(|| {
+                gamepad_buttons
+                    .get_just_pressed()
+                    .next()
+                    .map(|button| Binding::Gamepad(button.button_type))
+            });
+
+        if let Some(binding) = binding {
+            info!("Binding {} to {:?}", binding, action);
+            keymap.bind(action, binding);
+            screen.capturing = false;
+        }
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        screen.selected = screen
+            .selected
+            .checked_sub(1)
+            .unwrap_or(Action::ALL.len() - 1);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        screen.selected = (screen.selected + 1) % Action::ALL.len();
+    }
+    if keyboard.just_pressed(KeyCode::Enter) {
+        screen.capturing = true;
+    }
+    if keyboard.just_pressed(KeyCode::Delete) || keyboard.just_pressed(KeyCode::Backspace) {
+        keymap.clear(action);
+    }
+
+    if keyboard.just_pressed(KeyCode::Escape) {
+        for entity in query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        commands.remove_resource::<RebindScreen>();
+
+        let path = Path::new(KEYMAP_FILE);
+        match keymap.save(path) {
+            Ok(()) => info!("Saved keymap to {:?}", path),
+            Err(err) => error!("Failed to save keymap to {:?}: {}", path, err),
+        }
+    }
+}
+
+/// System to redraw the rebinding screen whenever the selection or [`KeyMap`] changes
+pub fn render_rebind_screen(
+    mut commands: Commands,
+    screen: Res<RebindScreen>,
+    keymap: Res<KeyMap>,
+    asset_store: Res<AssetStore>,
+    query: Query<Entity, With<RebindScreenMarker>>,
+) {
+    if !screen.is_changed() && !keymap.is_changed() {
+        return;
+    }
+
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let text_style = |color: Color| TextStyle {
+        font: asset_store.font.clone(),
+        font_size: REBIND_FONT_SIZE,
+        color,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(REBIND_PADDING)),
+                    ..default()
+                },
+                background_color: REBIND_BACKGROUND_COLOR.into(),
+                z_index: ZIndex::Global(i32::MAX),
+                ..default()
+            },
+            RebindScreenMarker,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Up/Down select, Enter add binding, Delete clear bindings, Escape close",
+                text_style(REBIND_TEXT_COLOR),
+            ));
+
+            for (index, action) in Action::ALL.iter().enumerate() {
+                let selected = index == screen.selected;
+                let bindings = if selected && screen.capturing {
+                    "press any key, mouse or gamepad button...".to_string()
+                } else {
+                    keymap
+                        .bindings(*action)
+                        .iter()
+                        .map(Binding::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                let color = if selected {
+                    REBIND_SELECTED_COLOR
+                } else {
+                    REBIND_TEXT_COLOR
+                };
+
+                parent.spawn(TextBundle::from_section(
+                    format!("{:?}: {}", action, bindings),
+                    text_style(color),
+                ));
+            }
+        });
+}
@@ -0,0 +1,25 @@
+pub mod asset_loader;
+pub mod board;
+pub mod bot;
+pub mod camera;
+pub mod card;
+pub mod cli;
+pub mod coordinates;
+pub mod cursor;
+pub mod daily;
+pub mod debug;
+pub mod headless;
+pub mod hint;
+pub mod hud;
+pub mod keys;
+pub mod menu;
+pub mod notation;
+pub mod profile;
+pub mod replay;
+pub mod schedule;
+pub mod settings;
+pub mod simulation;
+pub mod state;
+pub mod tournament;
+pub mod tourney;
+pub mod window;
@@ -1,38 +1,51 @@
-mod asset_loader;
-pub mod board;
-mod camera;
-pub mod card;
-mod coordinates;
-mod debug;
-mod keys;
-mod schedule;
-mod window;
+use bevy::{log::Level, prelude::*};
+use clap::Parser;
 
-use bevy::prelude::*;
-use board::plugin::BoardPlugin;
-use keys::KeyMap;
-
-use crate::card::plugin::CardPlugin;
-use asset_loader::AssetLoaderPlugin;
-use camera::CameraPlugin;
-use debug::DebugPlugin;
-use schedule::SchedulePlugin;
-use window::WindowPlugin;
+use pount::{
+    asset_loader::AssetLoaderPlugin, board::plugin::BoardPlugin, bot::plugin::BotPlugin,
+    camera::CameraPlugin, card::plugin::CardPlugin, cli::Cli, cursor::CursorPlugin,
+    daily::DailyPlugin, debug::DebugPlugin, headless::HeadlessPlugin, hint::HintPlugin,
+    hud::HudPlugin, keys::plugin::KeysPlugin, menu::plugin::MenuPlugin,
+    profile::plugin::ProfilePlugin, replay::plugin::ReplayPlugin, schedule::SchedulePlugin,
+    settings::plugin::SettingsPlugin, simulation::SimulationPlugin, state::StatePlugin,
+    tourney::plugin::TourneyPlugin, window::WindowPlugin,
+};
 
 fn main() {
-    App::new()
-        .insert_resource(ClearColor(Color::rgb(0.5, 0.5, 0.5)))
-        .insert_resource(KeyMap::default())
-        .insert_resource(AmbientLight {
-            color: Color::default(),
-            brightness: 0.15,
-        })
-        .add_plugins(WindowPlugin)
-        .add_plugins(CameraPlugin)
+    let cli = Cli::parse();
+    let mut app = App::new();
+
+    if cli.headless {
+        app.add_plugins(HeadlessPlugin {
+            log_level: Level::WARN,
+        });
+        cli.insert_resources(&mut app);
+        app.add_plugins(SimulationPlugin { games: 1 });
+        app.run();
+        return;
+    }
+
+    app.add_plugins(WindowPlugin);
+    cli.insert_resources(&mut app);
+    app.add_plugins(CameraPlugin)
+        .add_plugins(CursorPlugin)
+        .add_plugins(KeysPlugin)
+        .add_plugins(SettingsPlugin)
         .add_plugins(AssetLoaderPlugin)
         .add_plugins(DebugPlugin)
         .add_plugins(BoardPlugin)
         .add_plugins(CardPlugin)
+        .add_plugins(BotPlugin)
+        .add_plugins(HintPlugin)
+        .add_plugins(HudPlugin)
+        .add_plugins(ReplayPlugin)
+        .add_plugins(DailyPlugin)
+        .add_plugins(TourneyPlugin)
+        .add_plugins(ProfilePlugin)
+        .add_plugins(StatePlugin {
+            initial: cli.initial_state(),
+        })
+        .add_plugins(MenuPlugin)
         .add_plugins(SchedulePlugin)
         .run();
 }
@@ -0,0 +1,388 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    asset_loader::AssetStore,
+    board::{
+        bundle::{GameState, TILE_SIZE},
+        win_condition::{winner_name, winning_tiles},
+    },
+    bot::plugin::BotSeats,
+    card::{deck::DeckDefinition, sequence::CardSequence, spawn::CardIndex},
+    coordinates::{ActuallyLogicalCoordinates, BoardCoordinates},
+    daily::{BeforeDaily, DailyChallenge, LeaveDailyContext},
+    hint::HintsUsed,
+    keys::keymap::ActionInput,
+    profile::plugin::SeatProfiles,
+    replay::{file::Replay, record::ReplayRecorder, viewer::ReplayViewer},
+    state::AppState,
+};
+
+const GAME_OVER_FONT_SIZE: f32 = 30.0;
+const GAME_OVER_TITLE_FONT_SIZE: f32 = 60.0;
+const GAME_OVER_PADDING: f32 = 30.0;
+const GAME_OVER_GAP: f32 = 10.0;
+const GAME_OVER_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.85);
+const GAME_OVER_TEXT_COLOR: Color = Color::WHITE;
+const GAME_OVER_HEADER_COLOR: Color = Color::GOLD;
+const BUTTON_COLOR: Color = Color::rgb(0.15, 0.15, 0.2);
+const SELECTED_BUTTON_COLOR: Color = Color::rgb(0.3, 0.3, 0.45);
+const BUTTON_PADDING: f32 = 10.0;
+/// Below the overlay screens, which are drawn at `i32::MAX`
+const GAME_OVER_Z: i32 = i32::MAX - 1;
+/// Z at which the winning line is highlighted, above the cards
+const WINNING_LINE_Z: f32 = 60.0;
+const WINNING_LINE_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.35);
+
+/// Everything the game over screen offers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameOverEntry {
+    /// Same seats, new seed
+    Rematch,
+    /// Everyone moves up one seat, so somebody else starts
+    SwapSeats,
+    ViewReplay,
+    MainMenu,
+}
+
+impl GameOverEntry {
+    pub const ALL: [GameOverEntry; 4] = [
+        GameOverEntry::Rematch,
+        GameOverEntry::SwapSeats,
+        GameOverEntry::ViewReplay,
+        GameOverEntry::MainMenu,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GameOverEntry::Rematch => "Rematch",
+            GameOverEntry::SwapSeats => "Swap seats",
+            GameOverEntry::ViewReplay => "View replay",
+            GameOverEntry::MainMenu => "Main menu",
+        }
+    }
+}
+
+/// Present while the game over screen is shown
+#[derive(Resource, Debug, Default)]
+pub struct GameOverScreen {
+    /// Index into [`GameOverEntry::ALL`] of the selected button
+    pub selected: usize,
+    /// Why the last entry did not work out, e.g. there is no replay to view
+    pub status: Option<String>,
+}
+
+/// Marker component for the root node of the game over screen
+#[derive(Component)]
+pub struct GameOverMarker;
+
+/// Marker component for the highlights on the tiles of the winning line
+#[derive(Component)]
+pub struct WinningLineMarker;
+
+/// Button of the game over screen
+#[derive(Component)]
+pub struct GameOverButton(pub GameOverEntry);
+
+/// System to open the game over screen and highlight the winning line
+pub fn open_game_over_screen(mut commands: Commands, board_state: Res<GameState>) {
+    commands.insert_resource(GameOverScreen::default());
+
+    for (x, y) in winning_tiles(&board_state).into_iter().flatten() {
+        let mut coordinates: ActuallyLogicalCoordinates =
+            BoardCoordinates::from_xyz(x, y, 0).into();
+        coordinates.transform.translation.z = WINNING_LINE_Z;
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: WINNING_LINE_COLOR,
+                    custom_size: Some(Vec2::splat(TILE_SIZE)),
+                    ..default()
+                },
+                transform: coordinates.transform,
+                ..default()
+            },
+            WinningLineMarker,
+        ));
+    }
+}
+
+/// System to remove the game over screen, the winning line highlight goes with
+/// [`WinningLineMarker`]
+pub fn close_game_over_screen(mut commands: Commands, query: Query<Entity, With<GameOverMarker>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.remove_resource::<GameOverScreen>();
+}
+
+/// Group of all system parameters used to leave the game over screen
+#[derive(SystemParam)]
+pub struct GameOverContext<'w, 's> {
+    pub commands: Commands<'w, 's>,
+    pub bot_seats: ResMut<'w, BotSeats>,
+    pub seat_profiles: ResMut<'w, SeatProfiles>,
+    pub recorder: Res<'w, ReplayRecorder>,
+    pub next_state: ResMut<'w, NextState<AppState>>,
+    pub leave_daily: LeaveDailyContext<'w>,
+}
+
+impl GameOverContext<'_, '_> {
+    /// Do what the entry says, returns why it did not work out if it did not
+    pub fn pick(&mut self, entry: GameOverEntry) -> Option<String> {
+        match entry {
+            GameOverEntry::Rematch => {
+                // A rematch keeps the bots of the daily challenge, only the challenge ends
+                self.commands.remove_resource::<DailyChallenge>();
+                self.commands.remove_resource::<BeforeDaily>();
+                self.next_state.set(AppState::PlayerSetup);
+            }
+            GameOverEntry::SwapSeats => {
+                let num_seats = self.bot_seats.0.len();
+                self.bot_seats.0.rotate_right(1);
+                self.seat_profiles.0.resize(num_seats, None);
+                self.seat_profiles.0.rotate_right(1);
+                info!("Swapped seats to {:?}", self.bot_seats.setups());
+
+                self.commands.remove_resource::<DailyChallenge>();
+                self.commands.remove_resource::<BeforeDaily>();
+                self.next_state.set(AppState::PlayerSetup);
+            }
+            GameOverEntry::ViewReplay => {
+                let Some(path) = &self.recorder.path else {
+                    return Some("This game was not recorded".to_string());
+                };
+                match Replay::load(path) {
+                    Ok(replay) => {
+                        info!("Watching replay {:?}", path);
+                        self.leave_daily.leave(&mut self.commands);
+                        self.commands.insert_resource(ReplayViewer::new(replay));
+                        self.next_state.set(AppState::Replay);
+                    }
+                    Err(err) => {
+                        error!("Failed to load replay {:?}: {}", path, err);
+                        return Some(format!("Failed to load the replay: {}", err));
+                    }
+                }
+            }
+            GameOverEntry::MainMenu => self.next_state.set(AppState::MainMenu),
+        }
+        None
+    }
+}
+
+/// System to pick a button of the game over screen, by clicking it or with the arrows and enter.
+/// Taking back the last move goes on playing instead
+pub fn navigate_game_over_screen(
+    mut screen: ResMut<GameOverScreen>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input: ActionInput,
+    buttons: Query<(&Interaction, &GameOverButton), Changed<Interaction>>,
+    mut context: GameOverContext,
+) {
+    if input.is_blocked() {
+        return;
+    }
+
+    let num_entries = GameOverEntry::ALL.len();
+    if keyboard.just_pressed(KeyCode::ArrowLeft) || keyboard.just_pressed(KeyCode::ArrowUp) {
+        screen.selected = screen.selected.checked_sub(1).unwrap_or(num_entries - 1);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowRight) || keyboard.just_pressed(KeyCode::ArrowDown) {
+        screen.selected = (screen.selected + 1) % num_entries;
+    }
+
+    let mut picked = keyboard
+        .just_pressed(KeyCode::Enter)
+        .then(|| GameOverEntry::ALL[screen.selected]);
+    for (interaction, GameOverButton(entry)) in buttons.iter() {
+        match interaction {
+            Interaction::Pressed => picked = Some(*entry),
+            Interaction::Hovered => {
+                let index = GameOverEntry::ALL
+                    .iter()
+                    .position(|other| other == entry)
+                    .unwrap_or_default();
+                // Only touch the screen on an actual change, it is redrawn whenever it changes
+                if screen.selected != index {
+                    screen.selected = index;
+                }
+            }
+            Interaction::None => {}
+        }
+    }
+
+    if let Some(entry) = picked {
+        if let Some(status) = context.pick(entry) {
+            screen.status = Some(status);
+        }
+    }
+}
+
+/// Group of all system parameters used to sum up the finished game
+#[derive(SystemParam)]
+pub struct GameSummaryContext<'w> {
+    pub board_state: Res<'w, GameState>,
+    pub card_index: Res<'w, CardIndex>,
+    pub card_sequence: Res<'w, CardSequence>,
+    pub deck: Res<'w, DeckDefinition>,
+    pub seat_profiles: Res<'w, SeatProfiles>,
+    pub hints_used: Res<'w, HintsUsed>,
+    pub asset_store: Res<'w, AssetStore>,
+}
+
+impl GameSummaryContext<'_> {
+    /// Who won and with which line, e.g. `Red wins with R4 R7 R2 R9`
+    pub fn result(&self) -> (String, Color) {
+        let line: Vec<_> = winning_tiles(&self.board_state)
+            .into_iter()
+            .flatten()
+            .filter_map(|(x, y)| self.board_state.get_tile(x, y).cards.last().copied())
+            .collect();
+        match line.first() {
+            Some(card) => (
+                format!(
+                    "{} wins with {}",
+                    winner_name(card.color, &self.deck, &self.seat_profiles),
+                    line.iter()
+                        .map(|card| card.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                ),
+                card.color.to_color(),
+            ),
+            None => ("Draw, no cards left".to_string(), GAME_OVER_HEADER_COLOR),
+        }
+    }
+
+    /// Cards each seat had not placed yet, in turn order
+    pub fn cards_left(&self) -> Vec<usize> {
+        self.card_sequence
+            .cards_left(self.card_index.index, self.deck.num_players())
+    }
+}
+
+/// System to redraw the game over screen whenever the selection changes
+pub fn render_game_over_screen(
+    mut commands: Commands,
+    screen: Res<GameOverScreen>,
+    context: GameSummaryContext,
+    query: Query<Entity, With<GameOverMarker>>,
+) {
+    if !screen.is_changed() {
+        return;
+    }
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let text_style = |font_size: f32, color: Color| TextStyle {
+        font: context.asset_store.font.clone(),
+        font_size,
+        color,
+    };
+    let (result, result_color) = context.result();
+    let players = context.deck.players();
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                z_index: ZIndex::Global(GAME_OVER_Z),
+                ..default()
+            },
+            GameOverMarker,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::all(Val::Px(GAME_OVER_PADDING)),
+                        row_gap: Val::Px(GAME_OVER_GAP),
+                        ..default()
+                    },
+                    background_color: GAME_OVER_BACKGROUND_COLOR.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        result,
+                        text_style(GAME_OVER_TITLE_FONT_SIZE, result_color),
+                    ));
+                    parent.spawn(TextBundle::from_section(
+                        format!("{} cards placed", context.card_index.index),
+                        text_style(GAME_OVER_FONT_SIZE, GAME_OVER_TEXT_COLOR),
+                    ));
+                    parent.spawn(TextBundle::from_section(
+                        match context.hints_used.0 {
+                            1 => "1 hint used".to_string(),
+                            hints => format!("{} hints used", hints),
+                        },
+                        text_style(GAME_OVER_FONT_SIZE, GAME_OVER_TEXT_COLOR),
+                    ));
+                    for (seat, cards_left) in context.cards_left().into_iter().enumerate() {
+                        let name = context
+                            .seat_profiles
+                            .name(seat)
+                            .map(str::to_string)
+                            .unwrap_or_else(|| format!("Seat {}", seat + 1));
+                        parent.spawn(TextBundle::from_section(
+                            format!("{} {:?}: {} cards left", name, players[seat], cards_left),
+                            text_style(GAME_OVER_FONT_SIZE, GAME_OVER_TEXT_COLOR),
+                        ));
+                    }
+
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                column_gap: Val::Px(GAME_OVER_GAP),
+                                margin: UiRect::top(Val::Px(GAME_OVER_GAP)),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            for (index, entry) in GameOverEntry::ALL.into_iter().enumerate() {
+                                let color = if index == screen.selected {
+                                    SELECTED_BUTTON_COLOR
+                                } else {
+                                    BUTTON_COLOR
+                                };
+                                parent
+                                    .spawn((
+                                        ButtonBundle {
+                                            style: Style {
+                                                padding: UiRect::all(Val::Px(BUTTON_PADDING)),
+                                                ..default()
+                                            },
+                                            background_color: color.into(),
+                                            ..default()
+                                        },
+                                        GameOverButton(entry),
+                                    ))
+                                    .with_children(|parent| {
+                                        parent.spawn(TextBundle::from_section(
+                                            entry.label(),
+                                            text_style(GAME_OVER_FONT_SIZE, GAME_OVER_TEXT_COLOR),
+                                        ));
+                                    });
+                            }
+                        });
+
+                    parent.spawn(TextBundle::from_section(
+                        screen.status.clone().unwrap_or_else(|| {
+                            "Undo to take back the last move, Escape for the main menu".to_string()
+                        }),
+                        text_style(GAME_OVER_FONT_SIZE, GAME_OVER_TEXT_COLOR),
+                    ));
+                });
+        });
+}
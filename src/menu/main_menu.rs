@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+
+use crate::{
+    asset_loader::AssetStore,
+    keys::keymap::{Action, KeyMap},
+};
+
+const TITLE_FONT_SIZE: f32 = 120.0;
+const ENTRY_FONT_SIZE: f32 = 36.0;
+const ENTRY_GAP: f32 = 12.0;
+const MENU_BACKGROUND_COLOR: Color = Color::rgb(0.08, 0.08, 0.1);
+const TITLE_COLOR: Color = Color::GOLD;
+const ENTRY_COLOR: Color = Color::WHITE;
+/// Below the overlay screens, which are drawn at `i32::MAX`
+const MENU_Z: i32 = i32::MAX - 1;
+
+/// Entries of the main menu, each started by the first binding of its [`Action`]
+const MAIN_MENU_ENTRIES: [(Action, &str); 6] = [
+    (Action::Restart, "New game"),
+    (Action::Daily, "Daily challenge"),
+    (Action::Replay, "Watch the last replay"),
+    (Action::Stats, "Profiles"),
+    (Action::Options, "Options"),
+    (Action::Quit, "Quit"),
+];
+
+/// Marker component for the root node of the main menu
+#[derive(Component)]
+pub struct MainMenuMarker;
+
+/// Label of an [`Action`] with the binding that triggers it, e.g. `Enter  New game`
+pub fn entry_label(keymap: &KeyMap, action: Action, label: &str) -> String {
+    match keymap.bindings(action).first() {
+        Some(binding) => format!("{}  {}", binding, label),
+        None => label.to_string(),
+    }
+}
+
+/// System to show the main menu over the board
+pub fn spawn_main_menu(mut commands: Commands, keymap: Res<KeyMap>, asset_store: Res<AssetStore>) {
+    let text_style = |font_size: f32, color: Color| TextStyle {
+        font: asset_store.font.clone(),
+        font_size,
+        color,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(ENTRY_GAP),
+                    ..default()
+                },
+                background_color: MENU_BACKGROUND_COLOR.into(),
+                z_index: ZIndex::Global(MENU_Z),
+                ..default()
+            },
+            MainMenuMarker,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Pount",
+                text_style(TITLE_FONT_SIZE, TITLE_COLOR),
+            ));
+            for (action, label) in MAIN_MENU_ENTRIES {
+                parent.spawn(TextBundle::from_section(
+                    entry_label(&keymap, action, label),
+                    text_style(ENTRY_FONT_SIZE, ENTRY_COLOR),
+                ));
+            }
+        });
+}
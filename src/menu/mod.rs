@@ -0,0 +1,5 @@
+pub mod game_over;
+pub mod main_menu;
+pub mod pause;
+pub mod plugin;
+pub mod setup;
@@ -0,0 +1,249 @@
+use bevy::{app::AppExit, prelude::*};
+
+use crate::{
+    asset_loader::AssetStore,
+    keys::keymap::ActionInput,
+    replay::{file::new_save_path, record::GameRecordContext},
+    settings::options::OptionsScreen,
+    state::{AppState, Redeal},
+};
+
+const PAUSE_FONT_SIZE: f32 = 36.0;
+const PAUSE_GAP: f32 = 12.0;
+const PAUSE_ENTRY_WIDTH: f32 = 420.0;
+const PAUSE_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.6);
+const PAUSE_TEXT_COLOR: Color = Color::WHITE;
+const PAUSE_HEADER_COLOR: Color = Color::GOLD;
+const SELECTED_ENTRY_COLOR: Color = Color::rgb(0.25, 0.25, 0.35);
+/// Below the overlay screens, which are drawn at `i32::MAX`
+const PAUSE_Z: i32 = i32::MAX - 1;
+
+/// Everything the pause menu offers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseEntry {
+    Resume,
+    /// Deal the same cards again, with the same seats
+    RestartRound,
+    /// Write a [`SavedGame`] to continue later with `--load`
+    Save,
+    Settings,
+    QuitToMenu,
+    QuitGame,
+}
+
+impl PauseEntry {
+    pub const ALL: [PauseEntry; 6] = [
+        PauseEntry::Resume,
+        PauseEntry::RestartRound,
+        PauseEntry::Save,
+        PauseEntry::Settings,
+        PauseEntry::QuitToMenu,
+        PauseEntry::QuitGame,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PauseEntry::Resume => "Resume",
+            PauseEntry::RestartRound => "Restart round",
+            PauseEntry::Save => "Save game",
+            PauseEntry::Settings => "Settings",
+            PauseEntry::QuitToMenu => "Quit to menu",
+            PauseEntry::QuitGame => "Quit game",
+        }
+    }
+
+    /// Entries that lose the game in progress ask first
+    pub fn needs_confirmation(self) -> bool {
+        matches!(
+            self,
+            PauseEntry::RestartRound | PauseEntry::QuitToMenu | PauseEntry::QuitGame
+        )
+    }
+}
+
+/// Present while the game is paused
+#[derive(Resource, Debug, Default)]
+pub struct PauseMenu {
+    /// Index into [`PauseEntry::ALL`] of the selected entry
+    pub selected: usize,
+    /// Waiting for the selected entry to be confirmed
+    pub confirming: bool,
+    /// Outcome of the last entry, e.g. where the game was saved
+    pub status: Option<String>,
+}
+
+/// Marker component for the root node of the pause menu
+#[derive(Component)]
+pub struct PauseMarker;
+
+/// System to open the pause menu with resume selected
+pub fn open_pause_menu(mut commands: Commands) {
+    commands.insert_resource(PauseMenu::default());
+}
+
+/// System to remove the pause menu when the game goes on
+pub fn close_pause_menu(mut commands: Commands, query: Query<Entity, With<PauseMarker>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.remove_resource::<PauseMenu>();
+}
+
+/// Write the game so far to a new file, returns what happened
+fn save_game(context: &GameRecordContext) -> String {
+    let path = new_save_path();
+    match context.saved_game().save(&path) {
+        Ok(()) => {
+            info!("Saved game to {:?}", path);
+            format!("Saved, continue with --load {}", path.display())
+        }
+        Err(err) => {
+            error!("Failed to save game to {:?}: {}", path, err);
+            format!("Failed to save: {}", err)
+        }
+    }
+}
+
+/// System to pick an entry of the pause menu. Uses fixed keys like the other screens: arrows to
+/// select, enter to pick and escape to resume, or to cancel a confirmation
+pub fn navigate_pause_menu(
+    mut commands: Commands,
+    mut menu: ResMut<PauseMenu>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input: ActionInput,
+    context: GameRecordContext,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if input.is_blocked() {
+        return;
+    }
+
+    if menu.confirming {
+        if keyboard.just_pressed(KeyCode::Escape) {
+            menu.confirming = false;
+        }
+        if !keyboard.just_pressed(KeyCode::Enter) {
+            return;
+        }
+    } else {
+        if keyboard.just_pressed(KeyCode::Escape) {
+            next_state.set(AppState::InGame);
+            return;
+        }
+        if keyboard.just_pressed(KeyCode::ArrowUp) {
+            menu.selected = menu
+                .selected
+                .checked_sub(1)
+                .unwrap_or(PauseEntry::ALL.len() - 1);
+        }
+        if keyboard.just_pressed(KeyCode::ArrowDown) {
+            menu.selected = (menu.selected + 1) % PauseEntry::ALL.len();
+        }
+        if !keyboard.just_pressed(KeyCode::Enter) {
+            return;
+        }
+
+        let entry = PauseEntry::ALL[menu.selected];
+        if entry.needs_confirmation() {
+            menu.confirming = true;
+            return;
+        }
+    }
+
+    menu.confirming = false;
+    match PauseEntry::ALL[menu.selected] {
+        PauseEntry::Resume => next_state.set(AppState::InGame),
+        PauseEntry::RestartRound => {
+            commands.insert_resource(Redeal);
+            next_state.set(AppState::PlayerSetup);
+        }
+        PauseEntry::Save => menu.status = Some(save_game(&context)),
+        PauseEntry::Settings => commands.insert_resource(OptionsScreen::default()),
+        PauseEntry::QuitToMenu => next_state.set(AppState::MainMenu),
+        PauseEntry::QuitGame => {
+            exit.send(AppExit);
+        }
+    }
+}
+
+/// System to redraw the pause menu whenever the selection changes
+pub fn render_pause_menu(
+    mut commands: Commands,
+    menu: Res<PauseMenu>,
+    asset_store: Res<AssetStore>,
+    query: Query<Entity, With<PauseMarker>>,
+) {
+    if !menu.is_changed() {
+        return;
+    }
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let text_style = |color: Color| TextStyle {
+        font: asset_store.font.clone(),
+        font_size: PAUSE_FONT_SIZE,
+        color,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(PAUSE_GAP),
+                    ..default()
+                },
+                background_color: PAUSE_BACKGROUND_COLOR.into(),
+                z_index: ZIndex::Global(PAUSE_Z),
+                ..default()
+            },
+            PauseMarker,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Paused",
+                text_style(PAUSE_HEADER_COLOR),
+            ));
+            for (index, entry) in PauseEntry::ALL.into_iter().enumerate() {
+                let selected = index == menu.selected;
+                let label = if selected && menu.confirming {
+                    format!("{}? Enter confirm, Escape cancel", entry.label())
+                } else {
+                    entry.label().to_string()
+                };
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            min_width: Val::Px(PAUSE_ENTRY_WIDTH),
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        background_color: if selected {
+                            SELECTED_ENTRY_COLOR.into()
+                        } else {
+                            Color::NONE.into()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            label,
+                            text_style(PAUSE_TEXT_COLOR),
+                        ));
+                    });
+            }
+            if let Some(status) = &menu.status {
+                parent.spawn(TextBundle::from_section(
+                    status.clone(),
+                    text_style(PAUSE_TEXT_COLOR),
+                ));
+            }
+        });
+}
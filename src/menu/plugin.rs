@@ -0,0 +1,107 @@
+use bevy::{app::AppExit, prelude::*};
+
+use crate::{
+    daily::DailyChallenge,
+    keys::keymap::{Action, ActionInput},
+    schedule::InGameSet,
+    state::AppState,
+    tourney::standings::{close_standings, StandingsScreen},
+};
+
+use super::{
+    game_over::{
+        close_game_over_screen, navigate_game_over_screen, open_game_over_screen,
+        render_game_over_screen, GameOverScreen, WinningLineMarker,
+    },
+    main_menu::{spawn_main_menu, MainMenuMarker},
+    pause::{close_pause_menu, navigate_pause_menu, open_pause_menu, render_pause_menu, PauseMenu},
+    setup::{
+        close_setup_screen, navigate_setup_screen, open_setup_screen, render_setup_screen,
+        SetupScreen,
+    },
+};
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::MainMenu), spawn_main_menu);
+        app.add_systems(OnExit(AppState::MainMenu), despawn_screen::<MainMenuMarker>);
+        app.add_systems(
+            OnTransition {
+                from: AppState::MainMenu,
+                to: AppState::PlayerSetup,
+            },
+            open_setup_screen.run_if(not(resource_exists::<DailyChallenge>)),
+        );
+        app.add_systems(
+            Update,
+            (navigate_setup_screen, render_setup_screen)
+                .chain()
+                .run_if(resource_exists::<SetupScreen>)
+                .run_if(in_state(AppState::PlayerSetup))
+                .in_set(InGameSet::HandleMenu),
+        );
+        app.add_systems(OnExit(AppState::PlayerSetup), close_setup_screen);
+        app.add_systems(OnEnter(AppState::Paused), open_pause_menu);
+        app.add_systems(
+            Update,
+            (navigate_pause_menu, render_pause_menu)
+                .chain()
+                .run_if(resource_exists::<PauseMenu>)
+                .run_if(in_state(AppState::Paused))
+                .in_set(InGameSet::HandleMenu),
+        );
+        app.add_systems(OnExit(AppState::Paused), close_pause_menu);
+        app.add_systems(OnEnter(AppState::GameOver), open_game_over_screen);
+        app.add_systems(
+            Update,
+            (
+                // The tourney standings cover the screen until they are closed
+                navigate_game_over_screen
+                    .run_if(not(resource_exists::<StandingsScreen>))
+                    .before(close_standings),
+                render_game_over_screen,
+            )
+                .chain()
+                .run_if(resource_exists::<GameOverScreen>)
+                .run_if(in_state(AppState::GameOver))
+                .in_set(InGameSet::HandleMenu),
+        );
+        app.add_systems(
+            OnExit(AppState::GameOver),
+            (close_game_over_screen, despawn_screen::<WinningLineMarker>),
+        );
+        app.add_systems(Update, handle_menu_keys.in_set(InGameSet::HandleMenu));
+    }
+}
+
+/// System to despawn every screen marked with `T`, run on leaving the state showing it
+pub fn despawn_screen<T: Component>(mut commands: Commands, query: Query<Entity, With<T>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// System to go back one screen on quit, the main menu quits the game. The pause menu handles
+/// its own keys
+fn handle_menu_keys(
+    mut exit: EventWriter<AppExit>,
+    input: ActionInput,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if !input.just_pressed(Action::Quit) {
+        return;
+    }
+    match state.get() {
+        AppState::MainMenu => {
+            exit.send(AppExit);
+        }
+        AppState::InGame => next_state.set(AppState::Paused),
+        AppState::Paused => {}
+        AppState::PlayerSetup | AppState::GameOver | AppState::Replay => {
+            next_state.set(AppState::MainMenu)
+        }
+    }
+}
@@ -0,0 +1,518 @@
+use bevy::{prelude::*, window::ReceivedCharacter};
+
+use crate::{
+    asset_loader::AssetStore,
+    bot::{
+        difficulty::Difficulty,
+        plugin::{BotSeat, BotSeats},
+        strategy::{BotKind, BotSetup},
+    },
+    card::{
+        bundle::CardColor,
+        deck::{DeckDefinition, MIN_PLAYERS, PLAYER_COLORS},
+    },
+    keys::keymap::ActionInput,
+    profile::plugin::SeatProfiles,
+};
+
+const SETUP_FONT_SIZE: f32 = 30.0;
+const SETUP_PADDING: f32 = 40.0;
+const SETUP_ROW_GAP: f32 = 8.0;
+const SETUP_BACKGROUND_COLOR: Color = Color::rgb(0.08, 0.08, 0.1);
+const SETUP_TEXT_COLOR: Color = Color::WHITE;
+const SETUP_HEADER_COLOR: Color = Color::GOLD;
+const SELECTED_CELL_COLOR: Color = Color::rgb(0.25, 0.25, 0.35);
+const NAME_COLUMN_WIDTH: f32 = 280.0;
+const COLUMN_WIDTH: f32 = 180.0;
+/// Below the overlay screens, which are drawn at `i32::MAX`
+const SETUP_Z: i32 = i32::MAX - 1;
+const MAX_NAME_LENGTH: usize = 16;
+/// Columns of a seat row before its colors
+const NAME_COLUMN: usize = 0;
+const PLAYER_COLUMN: usize = 1;
+const DIFFICULTY_COLUMN: usize = 2;
+const FIRST_COLOR_COLUMN: usize = 3;
+/// Bots a seat cycles through after being played by a person
+const SETUP_BOTS: [BotKind; 3] = [BotKind::Random, BotKind::Greedy, BotKind::Mcts];
+
+/// Who plays a seat
+#[derive(Debug, Clone, PartialEq)]
+pub enum SeatPlayer {
+    Human,
+    Bot(BotSetup),
+    /// The [`ExternalBot`](crate::bot::external::ExternalBot) playing this seat of the last game,
+    /// it can not be changed here, only moved
+    Engine {
+        seat: usize,
+        name: String,
+    },
+}
+
+/// Everything chosen for a single seat
+#[derive(Debug, Clone)]
+pub struct SeatSetup {
+    /// Name of the [`Profile`](crate::profile::file::Profile) playing the seat, empty if untracked
+    pub name: String,
+    pub player: SeatPlayer,
+    /// Colors the seat owns, out of [`PLAYER_COLORS`]
+    pub colors: Vec<CardColor>,
+}
+
+/// Present while the seats of a new game are chosen, in turn order. The first row sets the number
+/// of seats, the last row starts the game
+#[derive(Resource, Debug, Clone)]
+pub struct SetupScreen {
+    pub seats: Vec<SeatSetup>,
+    pub colors_per_player: usize,
+    pub row: usize,
+    pub column: usize,
+}
+
+impl SetupScreen {
+    /// Seats as they were in the last game
+    pub fn from_game(deck: &DeckDefinition, bot_seats: &BotSeats, profiles: &SeatProfiles) -> Self {
+        let seats = deck
+            .players()
+            .into_iter()
+            .enumerate()
+            .map(|(seat, colors)| SeatSetup {
+                name: profiles.name(seat).unwrap_or_default().to_string(),
+                player: match bot_seats.0.get(seat).and_then(Option::as_ref) {
+                    Some(bot_seat) if bot_seat.setup.kind == BotKind::Engine => {
+                        SeatPlayer::Engine {
+                            seat,
+                            name: bot_seat.name.clone(),
+                        }
+                    }
+                    Some(bot_seat) => SeatPlayer::Bot(bot_seat.setup),
+                    None => SeatPlayer::Human,
+                },
+                colors,
+            })
+            .collect();
+        Self {
+            seats,
+            colors_per_player: deck.colors_per_player,
+            row: 0,
+            column: NAME_COLUMN,
+        }
+    }
+
+    pub fn max_seats(&self) -> usize {
+        PLAYER_COLORS.len() / self.colors_per_player
+    }
+
+    pub fn start_row(&self) -> usize {
+        self.seats.len() + 1
+    }
+
+    /// Seat on the selected row, if any
+    pub fn selected_seat(&self) -> Option<usize> {
+        (1..self.start_row())
+            .contains(&self.row)
+            .then(|| self.row - 1)
+    }
+
+    pub fn num_columns(&self) -> usize {
+        FIRST_COLOR_COLUMN + self.colors_per_player
+    }
+
+    /// Add or remove seats at the end, new seats get colors nobody owns yet. Seats are only added
+    /// while enough colors are free
+    pub fn set_num_seats(&mut self, num_seats: usize) {
+        let max_seats = self.max_seats();
+        if max_seats < MIN_PLAYERS {
+            return;
+        }
+        let num_seats = num_seats.clamp(MIN_PLAYERS, max_seats);
+        self.seats.truncate(num_seats);
+        while self.seats.len() < num_seats {
+            let free_colors = self.free_colors();
+            let Some(colors) = free_colors.get(..self.colors_per_player) else {
+                break;
+            };
+            let colors = colors.to_vec();
+            self.seats.push(SeatSetup {
+                name: String::new(),
+                player: SeatPlayer::Human,
+                colors,
+            });
+        }
+    }
+
+    /// [`PLAYER_COLORS`] no seat owns
+    pub fn free_colors(&self) -> Vec<CardColor> {
+        PLAYER_COLORS
+            .iter()
+            .copied()
+            .filter(|color| !self.seats.iter().any(|seat| seat.colors.contains(color)))
+            .collect()
+    }
+
+    /// Change the selected field of a seat to its next value
+    pub fn cycle(&mut self, seat: usize) {
+        match self.column {
+            NAME_COLUMN => {}
+            PLAYER_COLUMN => self.cycle_player(seat),
+            DIFFICULTY_COLUMN => self.cycle_difficulty(seat),
+            column => self.cycle_color(seat, column - FIRST_COLOR_COLUMN),
+        }
+    }
+
+    /// A person, then every bot in [`SETUP_BOTS`], keeping the difficulty. An engine stays
+    fn cycle_player(&mut self, seat: usize) {
+        let player = &mut self.seats[seat].player;
+        *player = match *player {
+            SeatPlayer::Engine { .. } => return,
+            SeatPlayer::Human => SeatPlayer::Bot(BotSetup::new(SETUP_BOTS[0])),
+            SeatPlayer::Bot(setup) => {
+                match SETUP_BOTS.iter().position(|kind| *kind == setup.kind) {
+                    Some(index) if index + 1 < SETUP_BOTS.len() => SeatPlayer::Bot(BotSetup {
+                        kind: SETUP_BOTS[index + 1],
+                        difficulty: setup.difficulty,
+                    }),
+                    _ => SeatPlayer::Human,
+                }
+            }
+        };
+    }
+
+    /// Full strength, then every [`Difficulty`] from easy to expert
+    fn cycle_difficulty(&mut self, seat: usize) {
+        if let SeatPlayer::Bot(setup) = &mut self.seats[seat].player {
+            setup.difficulty = match setup.difficulty {
+                None => Some(Difficulty::ALL[0]),
+                Some(difficulty) => Difficulty::ALL
+                    .iter()
+                    .position(|other| *other == difficulty)
+                    .and_then(|index| Difficulty::ALL.get(index + 1))
+                    .copied(),
+            };
+        }
+    }
+
+    /// Next color out of [`PLAYER_COLORS`], swapped with whoever owned it
+    fn cycle_color(&mut self, seat: usize, slot: usize) {
+        let color = self.seats[seat].colors[slot];
+        let index = PLAYER_COLORS
+            .iter()
+            .position(|other| *other == color)
+            .unwrap_or_default();
+        let next = PLAYER_COLORS[(index + 1) % PLAYER_COLORS.len()];
+
+        for other in self.seats.iter_mut() {
+            for owned in other.colors.iter_mut() {
+                if *owned == next {
+                    *owned = color;
+                }
+            }
+        }
+        self.seats[seat].colors[slot] = next;
+    }
+
+    /// Move a seat earlier or later in the turn order, the selection moves along
+    pub fn move_seat(&mut self, seat: usize, earlier: bool) {
+        let other = if earlier {
+            seat.checked_sub(1)
+        } else {
+            Some(seat + 1).filter(|other| *other < self.seats.len())
+        };
+        if let Some(other) = other {
+            self.seats.swap(seat, other);
+            self.row = other + 1;
+        }
+    }
+
+    /// Every seat's colors in turn order, as the [`DeckDefinition`] deals them
+    pub fn colors(&self) -> Vec<CardColor> {
+        self.seats
+            .iter()
+            .flat_map(|seat| seat.colors.iter().copied())
+            .collect()
+    }
+
+    pub fn bot_setups(&self) -> Vec<Option<BotSetup>> {
+        self.seats
+            .iter()
+            .map(|seat| match seat.player {
+                SeatPlayer::Human => None,
+                SeatPlayer::Bot(setup) => Some(setup),
+                SeatPlayer::Engine { .. } => Some(BotSetup::new(BotKind::Engine)),
+            })
+            .collect()
+    }
+
+    /// Seat the chosen bots, engines are taken over from the seats of the last game
+    pub fn bot_seats(&self, last_game: &mut BotSeats) -> BotSeats {
+        let seats = self
+            .seats
+            .iter()
+            .map(|seat| match seat.player {
+                SeatPlayer::Human => None,
+                SeatPlayer::Bot(setup) => Some(BotSeat::new(setup, setup.build()?)),
+                SeatPlayer::Engine { seat, .. } => last_game.0.get_mut(seat).and_then(Option::take),
+            })
+            .collect();
+        BotSeats(seats)
+    }
+
+    pub fn seat_profiles(&self) -> SeatProfiles {
+        SeatProfiles(
+            self.seats
+                .iter()
+                .map(|seat| Some(seat.name.clone()).filter(|name| !name.is_empty()))
+                .collect(),
+        )
+    }
+}
+
+/// Marker component for the root node of the setup screen
+#[derive(Component)]
+pub struct SetupScreenMarker;
+
+/// System to open the setup screen with the seats of the last game
+pub fn open_setup_screen(
+    mut commands: Commands,
+    deck: Res<DeckDefinition>,
+    bot_seats: Res<BotSeats>,
+    seat_profiles: Res<SeatProfiles>,
+) {
+    commands.insert_resource(SetupScreen::from_game(&deck, &bot_seats, &seat_profiles));
+}
+
+/// System to remove the setup screen when leaving it
+pub fn close_setup_screen(mut commands: Commands, query: Query<Entity, With<SetupScreenMarker>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.remove_resource::<SetupScreen>();
+}
+
+/// System to choose the seats. Uses fixed keys like the rebinding screen: arrows to select, left
+/// and right on the first row to change the number of seats, enter to change the selected field,
+/// page up and down to change the turn order and typing to edit a name. Enter on the last row
+/// seats everyone and deals the game
+pub fn navigate_setup_screen(
+    mut commands: Commands,
+    mut screen: ResMut<SetupScreen>,
+    mut deck: ResMut<DeckDefinition>,
+    mut bot_seats: ResMut<BotSeats>,
+    mut characters: EventReader<ReceivedCharacter>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input: ActionInput,
+) {
+    if input.is_blocked() {
+        characters.clear();
+        return;
+    }
+
+    let num_rows = screen.start_row() + 1;
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        screen.row = screen.row.checked_sub(1).unwrap_or(num_rows - 1);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        screen.row = (screen.row + 1) % num_rows;
+    }
+
+    match screen.selected_seat() {
+        None if screen.row == 0 => {
+            let num_seats = screen.seats.len();
+            if keyboard.just_pressed(KeyCode::ArrowLeft) {
+                screen.set_num_seats(num_seats - 1);
+            }
+            if keyboard.just_pressed(KeyCode::ArrowRight) {
+                screen.set_num_seats(num_seats + 1);
+            }
+        }
+        None => {
+            if keyboard.just_pressed(KeyCode::Enter) {
+                deck.colors = screen.colors();
+                info!(
+                    "Seating {:?} with colors {:?}",
+                    screen.bot_setups(),
+                    deck.colors
+                );
+                *bot_seats = screen.bot_seats(&mut bot_seats);
+                commands.insert_resource(screen.seat_profiles());
+                commands.remove_resource::<SetupScreen>();
+            }
+        }
+        Some(seat) => {
+            let num_columns = screen.num_columns();
+            if keyboard.just_pressed(KeyCode::ArrowLeft) {
+                screen.column = screen.column.checked_sub(1).unwrap_or(num_columns - 1);
+            }
+            if keyboard.just_pressed(KeyCode::ArrowRight) {
+                screen.column = (screen.column + 1) % num_columns;
+            }
+            if keyboard.just_pressed(KeyCode::Enter) {
+                screen.cycle(seat);
+            }
+            if keyboard.just_pressed(KeyCode::PageUp) {
+                screen.move_seat(seat, true);
+            }
+            if keyboard.just_pressed(KeyCode::PageDown) {
+                screen.move_seat(seat, false);
+            }
+
+            if screen.column == NAME_COLUMN {
+                let name = &mut screen.seats[seat].name;
+                if keyboard.just_pressed(KeyCode::Backspace) {
+                    name.pop();
+                }
+                for character in characters.read() {
+                    for char in character.char.chars().filter(|char| !char.is_control()) {
+                        if name.chars().count() < MAX_NAME_LENGTH {
+                            name.push(char);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    characters.clear();
+}
+
+/// System to redraw the setup screen whenever a choice changes
+pub fn render_setup_screen(
+    mut commands: Commands,
+    screen: Res<SetupScreen>,
+    asset_store: Res<AssetStore>,
+    query: Query<Entity, With<SetupScreenMarker>>,
+) {
+    if !screen.is_changed() {
+        return;
+    }
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let text_style = |color: Color| TextStyle {
+        font: asset_store.font.clone(),
+        font_size: SETUP_FONT_SIZE,
+        color,
+    };
+    let cell = |width: f32, selected: bool| NodeBundle {
+        style: Style {
+            width: Val::Px(width),
+            ..default()
+        },
+        background_color: if selected {
+            SELECTED_CELL_COLOR.into()
+        } else {
+            Color::NONE.into()
+        },
+        ..default()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(SETUP_PADDING)),
+                    row_gap: Val::Px(SETUP_ROW_GAP),
+                    ..default()
+                },
+                background_color: SETUP_BACKGROUND_COLOR.into(),
+                z_index: ZIndex::Global(SETUP_Z),
+                ..default()
+            },
+            SetupScreenMarker,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "New game, seats play in this order",
+                text_style(SETUP_HEADER_COLOR),
+            ));
+            parent.spawn(TextBundle::from_section(
+                "Arrows select, Enter change, Page Up/Down reorder, type a name to track its \
+                 profile, Escape back",
+                text_style(SETUP_TEXT_COLOR),
+            ));
+
+            parent
+                .spawn(cell(NAME_COLUMN_WIDTH * 2.0, screen.row == 0))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        format!("Seats  < {} >", screen.seats.len()),
+                        text_style(SETUP_TEXT_COLOR),
+                    ));
+                });
+
+            parent.spawn(NodeBundle::default()).with_children(|parent| {
+                for (index, header) in ["Name", "Player", "Difficulty", "Colors"]
+                    .into_iter()
+                    .enumerate()
+                {
+                    let width = if index == NAME_COLUMN {
+                        NAME_COLUMN_WIDTH
+                    } else {
+                        COLUMN_WIDTH
+                    };
+                    parent.spawn(cell(width, false)).with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            header,
+                            text_style(SETUP_HEADER_COLOR),
+                        ));
+                    });
+                }
+            });
+
+            for (seat, setup) in screen.seats.iter().enumerate() {
+                let selected_column =
+                    (screen.selected_seat() == Some(seat)).then_some(screen.column);
+                let name = if setup.name.is_empty() {
+                    format!("Seat {}", seat + 1)
+                } else {
+                    setup.name.clone()
+                };
+                let (player, difficulty) = match &setup.player {
+                    SeatPlayer::Human => ("Human".to_string(), "-".to_string()),
+                    SeatPlayer::Engine { name, .. } => (name.clone(), "-".to_string()),
+                    SeatPlayer::Bot(bot) => (
+                        bot.kind.to_string(),
+                        bot.difficulty
+                            .map(|difficulty| difficulty.to_string())
+                            .unwrap_or_else(|| "Full".to_string()),
+                    ),
+                };
+                let texts = [name, player, difficulty]
+                    .into_iter()
+                    .map(|text| (text, SETUP_TEXT_COLOR))
+                    .chain(
+                        setup
+                            .colors
+                            .iter()
+                            .map(|color| (format!("{:?}", color), color.to_color())),
+                    );
+
+                parent.spawn(NodeBundle::default()).with_children(|parent| {
+                    for (column, (text, color)) in texts.enumerate() {
+                        let width = if column == NAME_COLUMN {
+                            NAME_COLUMN_WIDTH
+                        } else {
+                            COLUMN_WIDTH
+                        };
+                        parent
+                            .spawn(cell(width, selected_column == Some(column)))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(text, text_style(color)));
+                            });
+                    }
+                });
+            }
+
+            parent
+                .spawn(cell(NAME_COLUMN_WIDTH, screen.row == screen.start_row()))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Start game",
+                        text_style(SETUP_HEADER_COLOR),
+                    ));
+                });
+        });
+}
@@ -0,0 +1,408 @@
+//! Compact text notation for cards, tiles and moves
+//!
+//! A [`Card`] is written as its color letter followed by its value (`G7`), a tile as its column
+//! letter followed by its row number (`c4`) and a [`Move`] as both joined by an `@` (`G7@c4`).
+//!
+//! A whole [`GameState`] is written similar to FEN: rows from the top row down separated by `/`,
+//! each row a comma separated list of tile stacks (bottom card first, e.g. `R3G7`) where runs of
+//! empty tiles are written as their count. The board is followed by the index of the next card in
+//! the [`CardSequence`](crate::card::sequence::CardSequence) and the player whose turn it is:
+//! `10/10/10/10/10/10/3,Y4,6/2,R3G7,7/10/10 3 1`
+
+use std::{fmt, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    board::bundle::{GameState, BOARD_SIZE},
+    card::bundle::{Card, CardColor},
+    coordinates::BoardCoordinates,
+};
+
+/// Separates the [`Card`] from the tile in a [`Move`]
+const MOVE_SEPARATOR: char = '@';
+/// Separates the rows of a position
+const ROW_SEPARATOR: char = '/';
+/// Separates the tiles in a row of a position
+const TILE_SEPARATOR: char = ',';
+/// Written instead of the tile of a [`Move`] that skipped its card
+const SKIPPED_TILE: &str = "-";
+
+/// Error returned when a string is not valid notation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotationError {
+    UnknownColor(char),
+    InvalidValue(String),
+    InvalidTile(String),
+    InvalidMove(String),
+    InvalidPosition(String),
+    Empty,
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::UnknownColor(letter) => write!(f, "unknown color letter '{}'", letter),
+            NotationError::InvalidValue(value) => write!(f, "invalid card value '{}'", value),
+            NotationError::InvalidTile(tile) => write!(f, "invalid tile '{}'", tile),
+            NotationError::InvalidMove(mv) => {
+                write!(f, "invalid move '{}', expected e.g. G7@c4", mv)
+            }
+            NotationError::InvalidPosition(reason) => write!(f, "invalid position: {}", reason),
+            NotationError::Empty => write!(f, "empty notation"),
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+impl CardColor {
+    /// Letter this [`CardColor`] is written as
+    pub fn letter(self) -> char {
+        match self {
+            CardColor::Red => 'R',
+            CardColor::Yellow => 'Y',
+            CardColor::Blue => 'B',
+            CardColor::Green => 'G',
+            CardColor::Purple => 'P',
+            CardColor::Orange => 'O',
+            CardColor::Grey => 'N',
+        }
+    }
+
+    pub fn from_letter(letter: char) -> Result<Self, NotationError> {
+        match letter.to_ascii_uppercase() {
+            'R' => Ok(CardColor::Red),
+            'Y' => Ok(CardColor::Yellow),
+            'B' => Ok(CardColor::Blue),
+            'G' => Ok(CardColor::Green),
+            'P' => Ok(CardColor::Purple),
+            'O' => Ok(CardColor::Orange),
+            'N' => Ok(CardColor::Grey),
+            _ => Err(NotationError::UnknownColor(letter)),
+        }
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.color.letter(), self.value)
+    }
+}
+
+impl FromStr for Card {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.trim().chars();
+        let letter = chars.next().ok_or(NotationError::Empty)?;
+        let color = CardColor::from_letter(letter)?;
+        let value = chars
+            .as_str()
+            .parse()
+            .ok()
+            .filter(|value| *value > 0)
+            .ok_or_else(|| NotationError::InvalidValue(chars.as_str().to_string()))?;
+        Ok(Card { value, color })
+    }
+}
+
+impl fmt::Display for BoardCoordinates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (x, y, _) = self.as_xys();
+        write!(f, "{}{}", (b'a' + x as u8) as char, y + 1)
+    }
+}
+
+impl FromStr for BoardCoordinates {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || NotationError::InvalidTile(s.to_string());
+
+        let mut chars = s.chars();
+        let column = chars.next().ok_or(NotationError::Empty)?;
+        if !column.is_ascii_lowercase() {
+            return Err(invalid());
+        }
+        let x = (column as u8 - b'a') as usize;
+        let y = chars
+            .as_str()
+            .parse::<usize>()
+            .ok()
+            .and_then(|row| row.checked_sub(1))
+            .ok_or_else(invalid)?;
+
+        if x >= BOARD_SIZE || y >= BOARD_SIZE {
+            return Err(invalid());
+        }
+        Ok(BoardCoordinates::from_xyz(x, y, 0))
+    }
+}
+
+/// A [`Card`] placed on a tile, or skipped because it fit nowhere (`G7@-`)
+#[derive(Debug, Clone)]
+pub struct Move {
+    pub card: Card,
+    pub coordinates: Option<BoardCoordinates>,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.coordinates {
+            Some(coordinates) => write!(f, "{}{}{}", self.card, MOVE_SEPARATOR, coordinates),
+            None => write!(f, "{}{}{}", self.card, MOVE_SEPARATOR, SKIPPED_TILE),
+        }
+    }
+}
+
+impl FromStr for Move {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (card, coordinates) = s
+            .split_once(MOVE_SEPARATOR)
+            .ok_or_else(|| NotationError::InvalidMove(s.to_string()))?;
+        let coordinates = match coordinates.trim() {
+            SKIPPED_TILE => None,
+            coordinates => Some(coordinates.parse()?),
+        };
+        Ok(Move {
+            card: card.parse()?,
+            coordinates,
+        })
+    }
+}
+
+impl GameState {
+    /// Write the stacks on every tile, without the next card and turn
+    pub fn board_notation(&self) -> String {
+        let mut rows = Vec::with_capacity(BOARD_SIZE);
+
+        for tiles in self.board().iter().rev() {
+            let mut row: Vec<String> = Vec::new();
+            let mut empty_tiles = 0;
+
+            for tile in tiles.iter() {
+                if tile.cards.is_empty() {
+                    empty_tiles += 1;
+                    continue;
+                }
+                if empty_tiles > 0 {
+                    row.push(empty_tiles.to_string());
+                    empty_tiles = 0;
+                }
+                row.push(tile.cards.iter().map(Card::to_string).collect());
+            }
+            if empty_tiles > 0 {
+                row.push(empty_tiles.to_string());
+            }
+
+            rows.push(row.join(&TILE_SEPARATOR.to_string()));
+        }
+
+        rows.join(&ROW_SEPARATOR.to_string())
+    }
+
+    /// Write the full position: every tile, the index of the next card and whose turn it is
+    pub fn to_notation(&self, next_card: usize, turn: usize) -> String {
+        format!("{} {} {}", self.board_notation(), next_card, turn)
+    }
+
+    /// Parse a position written by [`GameState::to_notation`], returning the board, the index of
+    /// the next card and whose turn it is
+    pub fn from_notation(notation: &str) -> Result<(Self, usize, usize), NotationError> {
+        let invalid = |reason: &str| NotationError::InvalidPosition(reason.to_string());
+
+        let mut fields = notation.split_whitespace();
+        let board = fields.next().ok_or(NotationError::Empty)?;
+        let next_card = fields
+            .next()
+            .ok_or_else(|| invalid("missing next card"))?
+            .parse()
+            .map_err(|_| invalid("next card is not a number"))?;
+        let turn = fields
+            .next()
+            .ok_or_else(|| invalid("missing turn"))?
+            .parse()
+            .map_err(|_| invalid("turn is not a number"))?;
+        if fields.next().is_some() {
+            return Err(invalid("trailing fields"));
+        }
+
+        let rows: Vec<&str> = board.split(ROW_SEPARATOR).collect();
+        if rows.len() != BOARD_SIZE {
+            return Err(invalid(&format!("expected {} rows", BOARD_SIZE)));
+        }
+
+        let mut game_state = GameState::reset();
+        for (row_index, row) in rows.iter().enumerate() {
+            let y = BOARD_SIZE - 1 - row_index;
+            let mut x: usize = 0;
+
+            for token in row.split(TILE_SEPARATOR) {
+                if token.starts_with(|c: char| c.is_ascii_digit()) {
+                    x = token
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|count| (1..=BOARD_SIZE).contains(count))
+                        .and_then(|count| x.checked_add(count))
+                        .ok_or_else(|| invalid(&format!("bad empty tile count '{}'", token)))?;
+                    continue;
+                }
+
+                if x >= BOARD_SIZE {
+                    return Err(invalid(&format!("row {} is too long", y + 1)));
+                }
+                let cards = parse_stack(token)?;
+                game_state.get_tile_mut(x, y).cards = cards;
+                x += 1;
+            }
+
+            if x != BOARD_SIZE {
+                return Err(invalid(&format!("row {} has {} tiles", y + 1, x)));
+            }
+        }
+
+        // get_tile_mut marks the board as non empty, even if no cards were placed
+        game_state.empty = game_state
+            .board()
+            .iter()
+            .flatten()
+            .all(|tile| tile.cards.is_empty());
+
+        Ok((game_state, next_card, turn))
+    }
+}
+
+/// Parse a tile stack such as `R3G7` into its [`Card`]s, bottom card first
+fn parse_stack(stack: &str) -> Result<Vec<Card>, NotationError> {
+    let mut cards = Vec::new();
+    let mut start = 0;
+
+    for (index, c) in stack.char_indices().skip(1) {
+        if c.is_ascii_alphabetic() {
+            cards.push(stack[start..index].parse()?);
+            start = index;
+        }
+    }
+    if stack.is_empty() {
+        return Err(NotationError::InvalidPosition("empty tile".to_string()));
+    }
+    cards.push(stack[start..].parse()?);
+
+    Ok(cards)
+}
+
+/// Serialize any value using its notation
+fn serialize_notation<T: fmt::Display, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// Deserialize any value from its notation
+fn deserialize_notation<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromStr<Err = NotationError>,
+    D: Deserializer<'de>,
+{
+    let notation = String::deserialize(deserializer)?;
+    notation.parse().map_err(de::Error::custom)
+}
+
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_notation(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_notation(deserializer)
+    }
+}
+
+impl Serialize for Move {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_notation(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Move {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_notation(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::card::deck::CARD_VALUES;
+
+    use super::*;
+
+    fn round_trip(notation: &str) {
+        let (game_state, next_card, turn) = GameState::from_notation(notation).unwrap();
+        assert_eq!(game_state.to_notation(next_card, turn), notation);
+    }
+
+    #[test]
+    fn empty_board_round_trips() {
+        let notation = "10/10/10/10/10/10/10/10/10/10 0 0";
+        round_trip(notation);
+        assert!(GameState::from_notation(notation).unwrap().0.empty);
+    }
+
+    #[test]
+    fn stacked_board_round_trips() {
+        round_trip("10/10/10/10/10/10/3,Y4,6/2,R3G7,7/10/10 3 1");
+    }
+
+    #[test]
+    fn full_board_round_trips() {
+        let row = ["R1"; BOARD_SIZE].join(",");
+        let board = vec![row; BOARD_SIZE].join("/");
+        round_trip(&format!("{} {} 0", board, BOARD_SIZE * BOARD_SIZE));
+    }
+
+    #[test]
+    fn cards_round_trip() {
+        for color in "RYBGPON".chars() {
+            for value in CARD_VALUES {
+                let notation = format!("{}{}", color, value);
+                assert_eq!(notation.parse::<Card>().unwrap().to_string(), notation);
+            }
+        }
+        assert_eq!("g7".parse::<Card>().unwrap().to_string(), "G7");
+        assert_eq!("R12".parse::<Card>().unwrap().to_string(), "R12");
+    }
+
+    #[test]
+    fn rejects_bad_cards() {
+        for notation in ["", "G", "G0", "G-3", "G1.5", "Gx", "X5"] {
+            assert!(notation.parse::<Card>().is_err(), "{}", notation);
+        }
+    }
+
+    #[test]
+    fn moves_round_trip() {
+        for notation in ["G7@c4", "N5@a1", "R1@j10", "B2@-"] {
+            assert_eq!(notation.parse::<Move>().unwrap().to_string(), notation);
+        }
+        for notation in ["G7c4", "G7@k1", "G7@a0", "G7@a11", "G0@a1"] {
+            assert!(notation.parse::<Move>().is_err(), "{}", notation);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_empty_tile_counts() {
+        for count in ["0", "11", "18446744073709551615", "99999999999999999999"] {
+            let board = format!("{}/10/10/10/10/10/10/10/10/10 0 0", count);
+            assert!(GameState::from_notation(&board).is_err(), "{}", count);
+        }
+        assert!(GameState::from_notation("5,6/10/10/10/10/10/10/10/10/10 0 0").is_err());
+    }
+}
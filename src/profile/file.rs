@@ -0,0 +1,210 @@
+use std::{error::Error, fs, path::Path};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::card::bundle::CardColor;
+
+/// File all [`Profiles`] are persisted to, relative to the working directory
+pub const PROFILES_FILE: &str = "profiles.ron";
+/// Elo rating of a new [`Profile`]
+pub const STARTING_ELO: f64 = 1200.0;
+/// Most Elo a single game can win or lose, split over all opponents
+const ELO_K_FACTOR: f64 = 32.0;
+
+/// A named local player and their statistics over all finished games
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub name: String,
+    pub games: u32,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    /// Games won with a line of each color
+    pub color_wins: Vec<(CardColor, u32)>,
+    /// Sum of the stack heights of every card this player placed, counting the card itself
+    pub stack_depth_total: u32,
+    pub cards_placed: u32,
+    /// Longest line of their own color this player ever made
+    pub longest_line: usize,
+    pub elo: f64,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            games: 0,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+            color_wins: Vec::new(),
+            stack_depth_total: 0,
+            cards_placed: 0,
+            longest_line: 0,
+            elo: STARTING_ELO,
+        }
+    }
+}
+
+impl Profile {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..default()
+        }
+    }
+
+    /// Average height of the stack a card of this player ends up on, 1 for an empty tile
+    pub fn average_stack_depth(&self) -> f32 {
+        self.stack_depth_total as f32 / self.cards_placed.max(1) as f32
+    }
+
+    pub fn win_percentage(&self) -> f32 {
+        100.0 * self.wins as f32 / self.games.max(1) as f32
+    }
+
+    pub fn add_color_win(&mut self, color: CardColor) {
+        match self.color_wins.iter_mut().find(|(c, _)| *c == color) {
+            Some((_, wins)) => *wins += 1,
+            None => self.color_wins.push((color, 1)),
+        }
+    }
+}
+
+/// What a single player did during a finished game
+#[derive(Debug, Clone, Default)]
+pub struct GameRecord {
+    /// Name of the [`Profile`]
+    pub name: String,
+    /// Points of the game: 1 for the winner, 0.5 for everyone on a draw and 0 for the losers
+    pub score: f64,
+    /// The color the line was won with, if this player won
+    pub winning_color: Option<CardColor>,
+    pub stack_depth_total: u32,
+    pub cards_placed: u32,
+    pub longest_line: usize,
+}
+
+/// Every known [`Profile`], loaded at startup and written back after every game
+#[derive(Resource, Debug, Default, Serialize, Deserialize)]
+pub struct Profiles(pub Vec<Profile>);
+
+impl Profiles {
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.0.iter().find(|profile| profile.name == name)
+    }
+
+    /// The [`Profile`] with this name, created if there is none yet
+    pub fn get_or_create(&mut self, name: &str) -> &mut Profile {
+        let index = match self.0.iter().position(|profile| profile.name == name) {
+            Some(index) => index,
+            None => {
+                info!("Creating profile {}", name);
+                self.0.push(Profile::new(name));
+                self.0.len() - 1
+            }
+        };
+        &mut self.0[index]
+    }
+
+    /// Add a finished game to the [`Profile`] of every player in it. Elo is updated as if every
+    /// pair of players played a game against each other, with the ratings from before the game
+    pub fn record_game(&mut self, records: &[GameRecord]) {
+        let ratings: Vec<f64> = records
+            .iter()
+            .map(|record| self.get_or_create(&record.name).elo)
+            .collect();
+        let opponents = records.len().saturating_sub(1).max(1) as f64;
+
+        for (index, record) in records.iter().enumerate() {
+            let elo_change: f64 = records
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != index)
+                .map(|(other, other_record)| {
+                    let expected = expected_score(ratings[index], ratings[other]);
+                    let actual = pairwise_score(record.score, other_record.score);
+                    ELO_K_FACTOR / opponents * (actual - expected)
+                })
+                .sum();
+
+            let profile = self.get_or_create(&record.name);
+            profile.games += 1;
+            match record.score {
+                score if score >= 1.0 => profile.wins += 1,
+                score if score > 0.0 => profile.draws += 1,
+                _ => profile.losses += 1,
+            }
+            if let Some(color) = record.winning_color {
+                profile.add_color_win(color);
+            }
+            profile.stack_depth_total += record.stack_depth_total;
+            profile.cards_placed += record.cards_placed;
+            profile.longest_line = profile.longest_line.max(record.longest_line);
+            profile.elo += elo_change;
+        }
+    }
+
+    /// Profiles with the highest Elo first
+    pub fn ranked(&self) -> Vec<&Profile> {
+        let mut ranked: Vec<&Profile> = self.0.iter().collect();
+        ranked.sort_by(|a, b| b.elo.total_cmp(&a.elo));
+        ranked
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+        Ok(ron::from_str(&contents)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Load the [`PROFILES_FILE`], or no profiles if there is none
+    pub fn load_or_default() -> Self {
+        let path = Path::new(PROFILES_FILE);
+        if !path.exists() {
+            return Profiles::default();
+        }
+
+        match Profiles::load(path) {
+            Ok(profiles) => profiles,
+            Err(err) => {
+                warn!(
+                    "Failed to load profiles {:?}, starting empty: {}",
+                    path, err
+                );
+                Profiles::default()
+            }
+        }
+    }
+
+    /// Write the [`PROFILES_FILE`], logging failures
+    pub fn save_to_file(&self) {
+        let path = Path::new(PROFILES_FILE);
+        if let Err(err) = self.save(path) {
+            error!("Failed to save profiles to {:?}: {}", path, err);
+        }
+    }
+}
+
+/// Expected score of a player rated `elo` against a player rated `opponent_elo`
+pub fn expected_score(elo: f64, opponent_elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent_elo - elo) / 400.0))
+}
+
+/// Score of one player against another: higher game points win, equal points draw
+fn pairwise_score(score: f64, opponent_score: f64) -> f64 {
+    if score > opponent_score {
+        1.0
+    } else if score < opponent_score {
+        0.0
+    } else {
+        0.5
+    }
+}
@@ -0,0 +1,143 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    board::{
+        bundle::GameState,
+        restart::DealNumber,
+        win_condition::{line_length, GameFinished, GameResult},
+    },
+    card::{deck::DeckDefinition, sequence::CardSequence, undo::CardHistory},
+    replay::viewer::ReplayViewer,
+    schedule::InGameSet,
+};
+
+use super::{
+    file::{GameRecord, Profiles},
+    stats::{close_stats_screen, open_stats_screen, render_stats_screen, StatsScreen},
+};
+
+/// Name of the [`Profile`](super::file::Profile) playing each seat, in turn order. Games are
+/// only tracked for seats with a profile
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SeatProfiles(pub Vec<Option<String>>);
+
+impl SeatProfiles {
+    pub fn name(&self, seat: usize) -> Option<&str> {
+        self.0.get(seat).and_then(Option::as_deref)
+    }
+}
+
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Profiles::load_or_default());
+        app.init_resource::<SeatProfiles>();
+        app.add_systems(
+            Update,
+            record_profile_game
+                .run_if(not(resource_exists::<ReplayViewer>))
+                .in_set(InGameSet::LogState),
+        );
+        app.add_systems(
+            Update,
+            (
+                open_stats_screen.run_if(not(resource_exists::<StatsScreen>)),
+                (close_stats_screen, render_stats_screen)
+                    .chain()
+                    .run_if(resource_exists::<StatsScreen>),
+            )
+                .chain()
+                .in_set(InGameSet::HandleMenu),
+        );
+    }
+}
+
+/// Group of all system parameters used to look back at a finished game
+#[derive(SystemParam)]
+pub struct FinishedGameContext<'w> {
+    pub deck: Res<'w, DeckDefinition>,
+    pub card_history: Res<'w, CardHistory>,
+    pub card_sequence: Res<'w, CardSequence>,
+    pub deal_number: Res<'w, DealNumber>,
+}
+
+impl FinishedGameContext<'_> {
+    /// What every seat with a profile did during the game, replayed from the [`CardHistory`] so
+    /// undone moves do not count
+    pub fn records(&self, result: GameResult, seat_profiles: &SeatProfiles) -> Vec<GameRecord> {
+        let players = self.deck.players();
+        let mut records: Vec<GameRecord> = (0..players.len())
+            .map(|seat| GameRecord {
+                name: seat_profiles.name(seat).unwrap_or_default().to_string(),
+                score: match result {
+                    GameResult::Win { color } if players[seat].contains(&color) => 1.0,
+                    GameResult::Win { .. } => 0.0,
+                    GameResult::Draw => 0.5,
+                },
+                winning_color: match result {
+                    GameResult::Win { color } if players[seat].contains(&color) => Some(color),
+                    _ => None,
+                },
+                ..default()
+            })
+            .collect();
+
+        let mut board_state = GameState::reset();
+        let placed_cards = self
+            .card_history
+            .0
+            .iter()
+            .flatten()
+            .zip(self.card_sequence.cards.iter());
+        for (turn, (placed_card, card)) in placed_cards.enumerate() {
+            let Some(placed_card) = placed_card else {
+                continue;
+            };
+            let (x, y, _) = placed_card.board_coordinates.as_xys();
+            let tile = board_state.get_tile_mut(x, y);
+            tile.cards.push(*card);
+            let stack_depth = tile.cards.len() as u32;
+
+            let record = &mut records[turn % players.len()];
+            record.stack_depth_total += stack_depth;
+            record.cards_placed += 1;
+            if !card.color.is_neutral() {
+                let length = line_length(&board_state, x, y, card.color);
+                record.longest_line = record.longest_line.max(length);
+            }
+        }
+
+        records.retain(|record| !record.name.is_empty());
+        records
+    }
+}
+
+/// System to add a finished game to the [`Profiles`] of its players and save them. A game
+/// finished again after an undo is only counted once
+fn record_profile_game(
+    mut game_finished: EventReader<GameFinished>,
+    seat_profiles: Res<SeatProfiles>,
+    mut profiles: ResMut<Profiles>,
+    context: FinishedGameContext,
+    mut recorded: Local<Option<DealNumber>>,
+) {
+    for GameFinished(result) in game_finished.read() {
+        if *recorded == Some(*context.deal_number) {
+            continue;
+        }
+        *recorded = Some(*context.deal_number);
+
+        let records = context.records(*result, &seat_profiles);
+        if records.is_empty() {
+            continue;
+        }
+        profiles.record_game(&records);
+        for record in records.iter() {
+            if let Some(profile) = profiles.get(&record.name) {
+                info!("Profile {} is now rated {:.0}", profile.name, profile.elo);
+            }
+        }
+        profiles.save_to_file();
+    }
+}
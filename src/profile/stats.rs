@@ -0,0 +1,152 @@
+use bevy::prelude::*;
+
+use crate::{
+    asset_loader::AssetStore,
+    keys::keymap::{Action, ActionInput},
+};
+
+use super::file::Profiles;
+
+const STATS_FONT_SIZE: f32 = 26.0;
+const STATS_PADDING: f32 = 40.0;
+const STATS_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.85);
+const STATS_TEXT_COLOR: Color = Color::WHITE;
+const STATS_HEADER_COLOR: Color = Color::GOLD;
+const NAME_COLUMN_WIDTH: f32 = 260.0;
+const NUMBER_COLUMN_WIDTH: f32 = 120.0;
+
+/// Present while the profile statistics are shown, regular [`Action`]s are not triggered
+/// meanwhile
+#[derive(Resource, Default)]
+pub struct StatsScreen;
+
+/// Marker component for the root node of the stats screen
+#[derive(Component)]
+pub struct StatsScreenMarker;
+
+/// System to open the stats screen
+pub fn open_stats_screen(mut commands: Commands, input: ActionInput) {
+    if input.just_pressed(Action::Stats) {
+        commands.insert_resource(StatsScreen);
+    }
+}
+
+/// System to close the stats screen with escape, like the other screens
+pub fn close_stats_screen(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    query: Query<Entity, With<StatsScreenMarker>>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) {
+        for entity in query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        commands.remove_resource::<StatsScreen>();
+    }
+}
+
+/// System to draw every [`Profile`](super::file::Profile), highest Elo first
+pub fn render_stats_screen(
+    mut commands: Commands,
+    screen: Res<StatsScreen>,
+    profiles: Res<Profiles>,
+    asset_store: Res<AssetStore>,
+    query: Query<Entity, With<StatsScreenMarker>>,
+) {
+    if !screen.is_added() && !profiles.is_changed() {
+        return;
+    }
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let text_style = |color: Color| TextStyle {
+        font: asset_store.font.clone(),
+        font_size: STATS_FONT_SIZE,
+        color,
+    };
+
+    let header = [
+        "Profile", "Elo", "Games", "Won", "Drawn", "Lost", "Win %", "Depth", "Line", "Colors",
+    ]
+    .map(str::to_string)
+    .to_vec();
+    let rows = profiles.ranked().into_iter().map(|profile| {
+        vec![
+            profile.name.clone(),
+            format!("{:.0}", profile.elo),
+            profile.games.to_string(),
+            profile.wins.to_string(),
+            profile.draws.to_string(),
+            profile.losses.to_string(),
+            format!("{:.0}", profile.win_percentage()),
+            format!("{:.2}", profile.average_stack_depth()),
+            profile.longest_line.to_string(),
+            profile
+                .color_wins
+                .iter()
+                .map(|(color, wins)| format!("{}{}", color.letter(), wins))
+                .collect::<Vec<_>>()
+                .join(" "),
+        ]
+    });
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(STATS_PADDING)),
+                    ..default()
+                },
+                background_color: STATS_BACKGROUND_COLOR.into(),
+                z_index: ZIndex::Global(i32::MAX),
+                ..default()
+            },
+            StatsScreenMarker,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Profiles, Escape close. Depth is the average stack a card lands on, Line the \
+                 longest line ever made, Colors the wins per color",
+                text_style(STATS_TEXT_COLOR),
+            ));
+            if profiles.0.is_empty() {
+                parent.spawn(TextBundle::from_section(
+                    "No profiles yet, play with --profile <name>",
+                    text_style(STATS_TEXT_COLOR),
+                ));
+            }
+
+            for (index, row) in std::iter::once(header).chain(rows).enumerate() {
+                let color = if index == 0 {
+                    STATS_HEADER_COLOR
+                } else {
+                    STATS_TEXT_COLOR
+                };
+                parent.spawn(NodeBundle::default()).with_children(|parent| {
+                    for (column, text) in row.into_iter().enumerate() {
+                        let width = if column == 0 {
+                            NAME_COLUMN_WIDTH
+                        } else {
+                            NUMBER_COLUMN_WIDTH
+                        };
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    min_width: Val::Px(width),
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(text, text_style(color)));
+                            });
+                    }
+                });
+            }
+        });
+}
@@ -0,0 +1,246 @@
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    board::win_condition::GameResult,
+    bot::strategy::BotSetup,
+    card::{
+        bundle::{Card, CardColor},
+        deck::DeckDefinition,
+        sequence::{CardSequence, GameSeed},
+        undo::CardHistory,
+    },
+    notation::Move,
+};
+
+/// Directory finished games are written to, relative to the working directory
+pub const REPLAY_DIR: &str = "replays";
+/// Directory games saved from the pause menu are written to, relative to the working directory
+pub const SAVE_DIR: &str = "saves";
+/// File extension used for replay files
+const REPLAY_EXTENSION: &str = "ron";
+
+/// A game in progress, continued with `--load <path>`. Every [`Replay`] loads as one too
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SavedGame {
+    /// [`GameSeed`] the deck was dealt from
+    pub seed: u64,
+    /// Colors held by each player, in turn order
+    pub players: Vec<Vec<CardColor>>,
+    /// The full [`CardSequence`] that was dealt for this game
+    pub deck: Vec<Card>,
+    /// Every placed [`Card`], in the order it was placed
+    pub moves: Vec<Move>,
+    /// Number of hints the players asked for
+    #[serde(default)]
+    pub hints: usize,
+    /// Bot playing each seat in turn order, [`None`] for the seats played by people
+    #[serde(default)]
+    pub bots: Vec<Option<BotSetup>>,
+    /// Name of the [`Profile`](crate::profile::file::Profile) playing each seat, if any
+    #[serde(default)]
+    pub profiles: Vec<Option<String>>,
+    /// Date of the daily challenge, if the game is one
+    #[serde(default)]
+    pub daily: Option<NaiveDate>,
+}
+
+impl SavedGame {
+    /// Build a [`SavedGame`] from the current game
+    pub fn from_game(
+        deck: &DeckDefinition,
+        game_seed: GameSeed,
+        card_history: &CardHistory,
+        card_sequence: &CardSequence,
+        hints: usize,
+    ) -> Self {
+        let moves = card_history
+            .0
+            .iter()
+            .flatten()
+            .zip(card_sequence.cards.iter())
+            .map(|(placed_card, card)| Move {
+                card: *card,
+                coordinates: placed_card
+                    .as_ref()
+                    .map(|placed_card| placed_card.board_coordinates.clone()),
+            })
+            .collect();
+
+        Self {
+            seed: game_seed.0,
+            players: deck.players(),
+            deck: card_sequence.cards.clone(),
+            moves,
+            hints,
+            ..Default::default()
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        save_ron(self, path)
+    }
+
+    /// Load a saved game, or the game of a [`Replay`]
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+        match ron::from_str::<Replay>(&contents) {
+            Ok(replay) => Ok(replay.game),
+            Err(_) => Ok(ron::from_str(&contents)?),
+        }
+    }
+}
+
+/// Everything needed to rebuild a finished game move by move. The game is nested rather than
+/// `#[serde(flatten)]`ed, RON can not read flattened structs back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub game: SavedGame,
+    pub result: GameResult,
+}
+
+impl Replay {
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        save_ron(self, path)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+        Ok(ron::from_str(&contents)?)
+    }
+}
+
+/// Write as pretty RON, creating the directory if needed
+fn save_ron(value: &impl Serialize, path: &Path) -> Result<(), Box<dyn Error>> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let contents = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())?;
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Path for a new file in this directory, named after the current time in milliseconds. A counter
+/// is added if that file already exists, so no file is overwritten
+fn timestamped_path(dir: &str) -> PathBuf {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default();
+    let mut path = Path::new(dir).join(format!("{}.{}", timestamp, REPLAY_EXTENSION));
+    let mut counter = 1;
+    while path.exists() {
+        path = Path::new(dir).join(format!("{}_{}.{}", timestamp, counter, REPLAY_EXTENSION));
+        counter += 1;
+    }
+    path
+}
+
+/// Path for a new replay file, named after the current time
+pub fn new_replay_path() -> PathBuf {
+    timestamped_path(REPLAY_DIR)
+}
+
+/// Path for a new saved game, named after the current time
+pub fn new_save_path() -> PathBuf {
+    timestamped_path(SAVE_DIR)
+}
+
+/// Path of the most recently written replay file, if there is any
+pub fn latest_replay_path() -> Option<PathBuf> {
+    fs::read_dir(REPLAY_DIR)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == REPLAY_EXTENSION))
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, prelude::World};
+
+    use crate::{
+        asset_loader::AssetStore,
+        board::{bundle::GameState, win_condition::PlayerWinEntity},
+        card::spawn::CardIndex,
+        replay::viewer::ReplayStepContext,
+    };
+
+    use super::*;
+
+    fn new_world(cards: &[Card]) -> World {
+        let mut world = World::new();
+        world.insert_resource(GameState::reset());
+        world.insert_resource(CardIndex::default());
+        world.insert_resource(CardHistory(None));
+        world.insert_resource(PlayerWinEntity(None));
+        world.insert_resource(AssetStore::default());
+        world.insert_resource(CardSequence {
+            cards: cards.to_vec(),
+        });
+        world
+    }
+
+    fn play(world: &mut World, moves: &[Move]) {
+        let moves = moves.to_vec();
+        world.run_system_once(
+            move |mut context: ReplayStepContext| {
+                while context.step_forward(&moves) {}
+            },
+        );
+    }
+
+    fn save(world: &World) -> SavedGame {
+        SavedGame::from_game(
+            &DeckDefinition::default(),
+            GameSeed(7),
+            world.resource::<CardHistory>(),
+            world.resource::<CardSequence>(),
+            0,
+        )
+    }
+
+    fn notation(moves: &[Move]) -> Vec<String> {
+        moves.iter().map(Move::to_string).collect()
+    }
+
+    #[test]
+    fn skipped_cards_survive_undo_and_saves() {
+        let moves: Vec<Move> = ["R1@a1", "Y2@-", "B3@b1"]
+            .iter()
+            .map(|notation| notation.parse().unwrap())
+            .collect();
+        let cards: Vec<Card> = moves.iter().map(|played| played.card).collect();
+        let mut world = new_world(&cards);
+        play(&mut world, &moves);
+        assert_eq!(world.resource::<CardIndex>().index, 3);
+
+        // Taking back the second placed card goes back to the card after the skip
+        world.run_system_once(|mut context: ReplayStepContext| context.step_back());
+        assert_eq!(world.resource::<CardIndex>().index, 2);
+        let saved = save(&world);
+        assert_eq!(notation(&saved.moves), ["R1@a1", "Y2@-"]);
+
+        let loaded: SavedGame = ron::from_str(&ron::to_string(&saved).unwrap()).unwrap();
+        assert_eq!(notation(&loaded.moves), notation(&saved.moves));
+        let mut resumed = new_world(&loaded.deck);
+        play(&mut resumed, &loaded.moves);
+        assert_eq!(resumed.resource::<CardIndex>().index, 2);
+        assert_eq!(
+            resumed.resource::<GameState>().board_notation(),
+            world.resource::<GameState>().board_notation()
+        );
+
+        // The skip goes along with the card placed before it
+        world.run_system_once(|mut context: ReplayStepContext| context.step_back());
+        assert_eq!(world.resource::<CardIndex>().index, 0);
+        assert!(world.resource::<GameState>().empty);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod file;
+pub mod plugin;
+pub mod record;
+pub mod resume;
+pub mod viewer;
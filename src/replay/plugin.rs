@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+use crate::{
+    board::bundle::setup_board,
+    card::sequence::generate_card_sequences,
+    schedule::InGameSet,
+    state::{can_start_game, AppState},
+};
+
+use super::{
+    record::{record_finished_game, ReplayRecorder},
+    resume::resume_saved_game,
+    viewer::{control_replay, start_replay, ReplayViewer},
+};
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayRecorder>();
+        app.add_systems(
+            Startup,
+            resume_saved_game
+                .after(setup_board)
+                .after(generate_card_sequences),
+        );
+        app.add_systems(
+            Update,
+            (
+                start_replay
+                    .run_if(can_start_game)
+                    .run_if(not(in_state(AppState::Replay))),
+                control_replay.run_if(in_state(AppState::Replay)),
+            )
+                .chain()
+                .in_set(InGameSet::MutateBoard),
+        );
+        // Watching a replay finishes it again, don't record that
+        app.add_systems(
+            Update,
+            record_finished_game
+                .run_if(not(resource_exists::<ReplayViewer>))
+                .in_set(InGameSet::LogState),
+        );
+    }
+}
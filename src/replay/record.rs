@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    board::win_condition::GameFinished,
+    bot::plugin::BotSeats,
+    card::{
+        deck::DeckDefinition,
+        sequence::{CardSequence, GameSeed},
+        undo::CardHistory,
+    },
+    daily::DailyChallenge,
+    hint::HintsUsed,
+    profile::plugin::SeatProfiles,
+};
+
+use super::file::{new_replay_path, Replay, SavedGame};
+
+/// Tracks the replay file of the current game, so finishing it again after an undo overwrites
+/// the same file instead of writing a new one
+#[derive(Resource, Default)]
+pub struct ReplayRecorder {
+    pub path: Option<PathBuf>,
+}
+
+/// Group of all system parameters needed to write down the current game
+#[derive(SystemParam)]
+pub struct GameRecordContext<'w> {
+    pub deck: Res<'w, DeckDefinition>,
+    pub game_seed: Res<'w, GameSeed>,
+    pub card_history: Res<'w, CardHistory>,
+    pub card_sequence: Res<'w, CardSequence>,
+    pub hints_used: Res<'w, HintsUsed>,
+    pub bot_seats: Res<'w, BotSeats>,
+    pub seat_profiles: Res<'w, SeatProfiles>,
+    pub daily: Option<Res<'w, DailyChallenge>>,
+}
+
+impl GameRecordContext<'_> {
+    /// The game so far, including who plays each seat
+    pub fn saved_game(&self) -> SavedGame {
+        SavedGame {
+            bots: self.bot_seats.setups(),
+            profiles: self.seat_profiles.0.clone(),
+            daily: self.daily.as_ref().map(|daily| daily.date),
+            ..SavedGame::from_game(
+                &self.deck,
+                *self.game_seed,
+                &self.card_history,
+                &self.card_sequence,
+                self.hints_used.0,
+            )
+        }
+    }
+}
+
+/// System to write a [`Replay`] of the current game once it finishes
+pub fn record_finished_game(
+    mut game_finished: EventReader<GameFinished>,
+    context: GameRecordContext,
+    mut recorder: ResMut<ReplayRecorder>,
+) {
+    for GameFinished(result) in game_finished.read() {
+        let replay = Replay {
+            game: context.saved_game(),
+            result: *result,
+        };
+        let path = recorder.path.get_or_insert_with(new_replay_path);
+
+        info!(
+            "Game summary: {:?} after {} cards, {} hints used",
+            result,
+            replay.game.moves.len(),
+            replay.game.hints
+        );
+        match replay.save(path) {
+            Ok(()) => info!("Saved replay to {:?}", path),
+            Err(err) => error!("Failed to save replay to {:?}: {}", path, err),
+        }
+    }
+}
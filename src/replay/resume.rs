@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+
+use crate::{
+    bot::{
+        plugin::{BotSeat, BotSeats},
+        strategy::BotKind,
+    },
+    card::{
+        deck::DeckDefinition,
+        sequence::{CardSequence, GameSeed},
+    },
+    cli::Cli,
+    daily::DailyChallenge,
+    profile::plugin::SeatProfiles,
+};
+
+use super::{file::SavedGame, viewer::ReplayStepContext};
+
+/// System to continue the [`SavedGame`] or [`Replay`](super::file::Replay) passed as
+/// `--load <path>`, placing all of its moves before the first turn. The bots, profiles and daily
+/// challenge of the saved game take their seats again, headless runs have no seats to restore
+pub fn resume_saved_game(
+    cli: Res<Cli>,
+    deck: Res<DeckDefinition>,
+    mut game_seed: ResMut<GameSeed>,
+    mut context: ReplayStepContext,
+    bot_seats: Option<ResMut<BotSeats>>,
+    seat_profiles: Option<ResMut<SeatProfiles>>,
+) {
+    let Some(path) = &cli.load else {
+        return;
+    };
+
+    let saved_game = match SavedGame::load(Path::new(path)) {
+        Ok(saved_game) => saved_game,
+        Err(err) => {
+            error!("Failed to load saved game {:?}: {}", path, err);
+            return;
+        }
+    };
+    if saved_game.players.len() != deck.num_players() {
+        warn!(
+            "Saved game {:?} has {} players, the deck has {}",
+            path,
+            saved_game.players.len(),
+            deck.num_players()
+        );
+    }
+
+    info!("Continuing saved game {:?}", path);
+    *game_seed = GameSeed(saved_game.seed);
+    context.commands.insert_resource(CardSequence {
+        cards: saved_game.deck.clone(),
+    });
+    while context.step_forward(&saved_game.moves) {}
+
+    if let Some(mut bot_seats) = bot_seats.filter(|_| !saved_game.bots.is_empty()) {
+        *bot_seats = restore_bot_seats(&saved_game, !cli.engines.is_empty());
+    }
+    if let Some(mut seat_profiles) = seat_profiles.filter(|_| !saved_game.profiles.is_empty()) {
+        seat_profiles.0 = saved_game.profiles.clone();
+    }
+    if let Some(date) = saved_game.daily {
+        context.commands.insert_resource(DailyChallenge { date });
+    }
+}
+
+/// Seat the bots of the saved game. Engines can not be started from a save, a seat that was played
+/// by one waits for the engine started with `--engine` for it
+fn restore_bot_seats(saved_game: &SavedGame, has_engines: bool) -> BotSeats {
+    let seats = saved_game
+        .bots
+        .iter()
+        .enumerate()
+        .map(|(seat, setup)| {
+            let setup = (*setup)?;
+            if setup.kind == BotKind::Engine && !has_engines {
+                warn!(
+                    "Seat {} was played by an engine, start it with --engine",
+                    seat + 1
+                );
+            }
+            Some(BotSeat::new(setup, setup.build()?))
+        })
+        .collect();
+    BotSeats(seats)
+}
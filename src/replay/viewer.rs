@@ -0,0 +1,173 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    asset_loader::AssetStore,
+    board::{bundle::GameState, win_condition::PlayerWinEntity},
+    card::{
+        spawn::{
+            record_skipped_card, render_next_card, skip_next_card, update_board_state, CardIndex,
+        },
+        undo::{handle_undo, CardHistory},
+    },
+    daily::LeaveDailyContext,
+    keys::keymap::{Action, ActionInput},
+    notation::Move,
+    state::AppState,
+};
+
+use super::file::{latest_replay_path, Replay};
+
+/// Seconds between two moves during autoplay, at normal speed
+const AUTOPLAY_STEP_SECONDS: f32 = 1.0;
+const AUTOPLAY_MIN_SPEED: f32 = 0.25;
+const AUTOPLAY_MAX_SPEED: f32 = 16.0;
+
+/// Present while a [`Replay`] is being watched in [`AppState::Replay`]
+#[derive(Resource)]
+pub struct ReplayViewer {
+    pub replay: Replay,
+    pub autoplay: bool,
+    /// Autoplay speed multiplier
+    pub speed: f32,
+    pub timer: Timer,
+}
+
+impl ReplayViewer {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            autoplay: false,
+            speed: 1.0,
+            timer: Timer::from_seconds(AUTOPLAY_STEP_SECONDS, TimerMode::Repeating),
+        }
+    }
+}
+
+/// Group of all system parameters used to step through a [`Replay`]
+#[derive(SystemParam)]
+pub struct ReplayStepContext<'w, 's> {
+    pub commands: Commands<'w, 's>,
+    pub card_index: ResMut<'w, CardIndex>,
+    pub board_state: ResMut<'w, GameState>,
+    pub card_history: ResMut<'w, CardHistory>,
+    pub player_win_entity: ResMut<'w, PlayerWinEntity>,
+    pub asset_store: Res<'w, AssetStore>,
+}
+
+impl ReplayStepContext<'_, '_> {
+    /// Place the next of these moves, returns false if there are no moves left
+    pub fn step_forward(&mut self, moves: &[Move]) -> bool {
+        let Some(next_move) = moves.get(self.card_index.index).cloned() else {
+            return false;
+        };
+
+        info!("Replaying {}", next_move);
+        let Some(spawn_coordinates) = next_move.coordinates else {
+            skip_next_card(&mut self.board_state, &mut self.card_index);
+            record_skipped_card(&mut self.card_history);
+            return true;
+        };
+        update_board_state(
+            &spawn_coordinates,
+            &next_move.card,
+            &mut self.board_state,
+            &mut self.card_index,
+        );
+        render_next_card(
+            spawn_coordinates,
+            next_move.card,
+            &self.board_state,
+            &self.asset_store,
+            &mut self.commands,
+            &mut self.card_history,
+        );
+        true
+    }
+
+    /// Take back the last placed move, returns false if the board is already empty
+    pub fn step_back(&mut self) -> bool {
+        match self.card_history.0.as_mut() {
+            Some(placed_cards) if placed_cards.iter().any(Option::is_some) => {
+                handle_undo(
+                    &mut self.commands,
+                    placed_cards,
+                    &mut self.board_state,
+                    &mut self.player_win_entity,
+                    &mut self.card_index,
+                );
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// System to load the most recent [`Replay`] and start watching it
+pub fn start_replay(
+    mut commands: Commands,
+    input: ActionInput,
+    mut leave_daily: LeaveDailyContext,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if !input.just_pressed(Action::Replay) {
+        return;
+    }
+
+    let Some(path) = latest_replay_path() else {
+        warn!("No replays found to watch");
+        return;
+    };
+
+    match Replay::load(&path) {
+        Ok(replay) => {
+            info!("Watching replay {:?}, result: {:?}", path, replay.result);
+            leave_daily.leave(&mut commands);
+            commands.insert_resource(ReplayViewer::new(replay));
+            next_state.set(AppState::Replay);
+        }
+        Err(err) => error!("Failed to load replay {:?}: {}", path, err),
+    }
+}
+
+/// System to step through the [`Replay`] being watched
+pub fn control_replay(
+    input: ActionInput,
+    time: Res<Time>,
+    mut viewer: ResMut<ReplayViewer>,
+    mut context: ReplayStepContext,
+) {
+    if input.just_pressed(Action::ReplayForward) {
+        context.step_forward(&viewer.replay.game.moves);
+    }
+    if input.just_pressed(Action::ReplayBack) {
+        context.step_back();
+    }
+    if input.just_pressed(Action::ReplayStart) {
+        while context.step_back() {}
+    }
+    if input.just_pressed(Action::ReplayEnd) {
+        while context.step_forward(&viewer.replay.game.moves) {}
+    }
+    if input.just_pressed(Action::ReplayAutoplay) {
+        viewer.autoplay = !viewer.autoplay;
+        viewer.timer.reset();
+    }
+    if input.just_pressed(Action::ReplayFaster) {
+        viewer.speed = (viewer.speed * 2.0).min(AUTOPLAY_MAX_SPEED);
+        info!("Replay speed {}x", viewer.speed);
+    }
+    if input.just_pressed(Action::ReplaySlower) {
+        viewer.speed = (viewer.speed / 2.0).max(AUTOPLAY_MIN_SPEED);
+        info!("Replay speed {}x", viewer.speed);
+    }
+
+    if viewer.autoplay {
+        let delta = time.delta().mul_f32(viewer.speed);
+        if viewer.timer.tick(delta).just_finished()
+            && !context.step_forward(&viewer.replay.game.moves)
+        {
+            // Reached the end of the replay
+            viewer.autoplay = false;
+        }
+    }
+}
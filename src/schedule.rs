@@ -1,8 +1,11 @@
 use bevy::prelude::*;
 
+use crate::state::{board_visible, AppState};
+
 #[derive(SystemSet, Hash, Debug, Clone, Eq, PartialEq)]
 pub enum InGameSet {
     MutateCamera,
+    MoveCursor,
     MutateBoard,
     CheckWincondition,
     LogState,
@@ -17,6 +20,7 @@ impl Plugin for SchedulePlugin {
             Update,
             (
                 InGameSet::MutateCamera,
+                InGameSet::MoveCursor,
                 InGameSet::CheckWincondition,
                 // Want to flush in between these systemsets? look at the comment below
                 InGameSet::MutateBoard,
@@ -24,6 +28,16 @@ impl Plugin for SchedulePlugin {
             )
                 .chain(),
         );
+        // Gameplay only goes on in game, the camera only moves while there is a board to look at
+        app.configure_sets(
+            Update,
+            (
+                InGameSet::MutateCamera.run_if(board_visible),
+                InGameSet::MoveCursor.run_if(in_state(AppState::InGame)),
+                InGameSet::CheckWincondition
+                    .run_if(in_state(AppState::InGame).or_else(in_state(AppState::Replay))),
+            ),
+        );
         // app.add_systems(
         //     Update,
         //     apply_deferred
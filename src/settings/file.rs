@@ -0,0 +1,94 @@
+use std::{error::Error, fs, path::Path};
+
+use bevy::{prelude::*, window::WindowMode};
+use serde::{Deserialize, Serialize};
+
+/// File the [`Settings`] are persisted to, relative to the working directory
+pub const SETTINGS_FILE: &str = "settings.ron";
+
+/// Window and display options, loaded at startup and written back whenever they change
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub window_mode: WindowMode,
+    /// Window size in logical pixels, used when not in fullscreen
+    pub resolution: (f32, f32),
+    pub vsync: bool,
+    pub background_color: Color,
+    pub ambient_brightness: f32,
+    pub ui_scale: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            window_mode: WindowMode::Fullscreen,
+            resolution: (1920.0, 1080.0),
+            vsync: true,
+            background_color: Color::rgb(0.5, 0.5, 0.5),
+            ambient_brightness: 0.15,
+            ui_scale: 1.0,
+        }
+    }
+}
+
+impl Settings {
+    /// Load [`Settings`], options missing from the file keep their default value
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+        Ok(ron::from_str(&contents)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Load the [`SETTINGS_FILE`], or the default [`Settings`] if there is none
+    pub fn load_or_default() -> Self {
+        let path = Path::new(SETTINGS_FILE);
+        if !path.exists() {
+            return Settings::default();
+        }
+
+        match Settings::load(path) {
+            Ok(settings) => settings,
+            Err(err) => {
+                warn!(
+                    "Failed to load settings {:?}, using the default: {}",
+                    path, err
+                );
+                Settings::default()
+            }
+        }
+    }
+
+    /// Write the [`Settings`] to the [`SETTINGS_FILE`], logging failures
+    pub fn save_to_file(&self) {
+        let path = Path::new(SETTINGS_FILE);
+        match self.save(path) {
+            Ok(()) => info!("Saved settings to {:?}", path),
+            Err(err) => error!("Failed to save settings to {:?}: {}", path, err),
+        }
+    }
+
+    pub fn present_mode(&self) -> bevy::window::PresentMode {
+        if self.vsync {
+            bevy::window::PresentMode::AutoVsync
+        } else {
+            bevy::window::PresentMode::AutoNoVsync
+        }
+    }
+
+    /// Primary [`Window`] described by these [`Settings`]
+    pub fn window(&self) -> Window {
+        Window {
+            resolution: self.resolution.into(),
+            title: "pount".into(),
+            mode: self.window_mode,
+            present_mode: self.present_mode(),
+            ..default()
+        }
+    }
+}
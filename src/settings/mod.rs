@@ -0,0 +1,3 @@
+pub mod file;
+pub mod options;
+pub mod plugin;
@@ -0,0 +1,219 @@
+use bevy::{prelude::*, window::WindowMode};
+
+use crate::{
+    asset_loader::AssetStore,
+    keys::keymap::{Action, ActionInput},
+};
+
+use super::file::Settings;
+
+const OPTIONS_FONT_SIZE: f32 = 30.0;
+const OPTIONS_PADDING: f32 = 40.0;
+const OPTIONS_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.85);
+const OPTIONS_TEXT_COLOR: Color = Color::WHITE;
+const OPTIONS_SELECTED_COLOR: Color = Color::GOLD;
+
+const WINDOW_MODES: [WindowMode; 3] = [
+    WindowMode::Windowed,
+    WindowMode::BorderlessFullscreen,
+    WindowMode::Fullscreen,
+];
+const RESOLUTIONS: [(f32, f32); 5] = [
+    (1280.0, 720.0),
+    (1600.0, 900.0),
+    (1920.0, 1080.0),
+    (2560.0, 1440.0),
+    (3840.0, 2160.0),
+];
+const BACKGROUND_COLORS: [Color; 4] = [
+    Color::rgb(0.5, 0.5, 0.5),
+    Color::rgb(0.2, 0.2, 0.2),
+    Color::rgb(0.05, 0.05, 0.05),
+    Color::rgb(0.15, 0.2, 0.3),
+];
+const UI_SCALE_STEP: f32 = 0.1;
+const UI_SCALE_RANGE: (f32, f32) = (0.5, 3.0);
+
+/// Every option that can be changed on the options screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsOption {
+    WindowMode,
+    Resolution,
+    VSync,
+    BackgroundColor,
+    UiScale,
+}
+
+impl SettingsOption {
+    pub const ALL: [SettingsOption; 5] = [
+        SettingsOption::WindowMode,
+        SettingsOption::Resolution,
+        SettingsOption::VSync,
+        SettingsOption::BackgroundColor,
+        SettingsOption::UiScale,
+    ];
+
+    /// Change the option to its next (`step` 1) or previous (`step` -1) value
+    pub fn change(&self, settings: &mut Settings, step: i32) {
+        match self {
+            SettingsOption::WindowMode => {
+                settings.window_mode = cycle(&WINDOW_MODES, &settings.window_mode, step);
+            }
+            SettingsOption::Resolution => {
+                settings.resolution = cycle(&RESOLUTIONS, &settings.resolution, step);
+            }
+            SettingsOption::VSync => settings.vsync = !settings.vsync,
+            SettingsOption::BackgroundColor => {
+                settings.background_color =
+                    cycle(&BACKGROUND_COLORS, &settings.background_color, step);
+            }
+            SettingsOption::UiScale => {
+                settings.ui_scale = (settings.ui_scale + UI_SCALE_STEP * step as f32)
+                    .clamp(UI_SCALE_RANGE.0, UI_SCALE_RANGE.1);
+            }
+        }
+    }
+
+    pub fn value(&self, settings: &Settings) -> String {
+        match self {
+            SettingsOption::WindowMode => format!("{:?}", settings.window_mode),
+            SettingsOption::Resolution => {
+                format!("{}x{}", settings.resolution.0, settings.resolution.1)
+            }
+            SettingsOption::VSync => if settings.vsync { "On" } else { "Off" }.to_string(),
+            SettingsOption::BackgroundColor => {
+                let [r, g, b, _] = settings.background_color.as_rgba_f32();
+                format!("{:.2} {:.2} {:.2}", r, g, b)
+            }
+            SettingsOption::UiScale => format!("{:.1}", settings.ui_scale),
+        }
+    }
+}
+
+/// Next or previous value in `values`, starting at the first value if `current` is not one of them
+fn cycle<T: Copy + PartialEq>(values: &[T], current: &T, step: i32) -> T {
+    let len = values.len() as i32;
+    let index = match values.iter().position(|value| value == current) {
+        Some(index) => (index as i32 + step).rem_euclid(len),
+        None => 0,
+    };
+    values[index as usize]
+}
+
+/// Present while the options screen is open, regular [`Action`]s are not triggered meanwhile
+#[derive(Resource, Default)]
+pub struct OptionsScreen {
+    /// Index into [`SettingsOption::ALL`] of the selected option
+    pub selected: usize,
+}
+
+/// Marker component for the root node of the options screen
+#[derive(Component)]
+pub struct OptionsScreenMarker;
+
+/// System to open the options screen
+pub fn open_options_screen(mut commands: Commands, input: ActionInput) {
+    if input.just_pressed(Action::Options) {
+        commands.insert_resource(OptionsScreen::default());
+    }
+}
+
+/// System to navigate the options screen, every change is written to the settings file right away.
+/// Uses fixed keys like the rebinding screen: up/down to select, left/right to change the selected
+/// option and escape to close
+pub fn navigate_options_screen(
+    mut commands: Commands,
+    mut screen: ResMut<OptionsScreen>,
+    mut settings: ResMut<Settings>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    query: Query<Entity, With<OptionsScreenMarker>>,
+) {
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        screen.selected = screen
+            .selected
+            .checked_sub(1)
+            .unwrap_or(SettingsOption::ALL.len() - 1);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        screen.selected = (screen.selected + 1) % SettingsOption::ALL.len();
+    }
+
+    let step = if keyboard.just_pressed(KeyCode::ArrowRight) {
+        1
+    } else if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        -1
+    } else {
+        0
+    };
+    if step != 0 {
+        SettingsOption::ALL[screen.selected].change(&mut settings, step);
+        settings.save_to_file();
+    }
+
+    if keyboard.just_pressed(KeyCode::Escape) {
+        for entity in query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        commands.remove_resource::<OptionsScreen>();
+    }
+}
+
+/// System to redraw the options screen whenever the selection or [`Settings`] change
+pub fn render_options_screen(
+    mut commands: Commands,
+    screen: Res<OptionsScreen>,
+    settings: Res<Settings>,
+    asset_store: Res<AssetStore>,
+    query: Query<Entity, With<OptionsScreenMarker>>,
+) {
+    if !screen.is_changed() && !settings.is_changed() {
+        return;
+    }
+
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let text_style = |color: Color| TextStyle {
+        font: asset_store.font.clone(),
+        font_size: OPTIONS_FONT_SIZE,
+        color,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(OPTIONS_PADDING)),
+                    ..default()
+                },
+                background_color: OPTIONS_BACKGROUND_COLOR.into(),
+                z_index: ZIndex::Global(i32::MAX),
+                ..default()
+            },
+            OptionsScreenMarker,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Up/Down select, Left/Right change, Escape close",
+                text_style(OPTIONS_TEXT_COLOR),
+            ));
+
+            for (index, option) in SettingsOption::ALL.iter().enumerate() {
+                let color = if index == screen.selected {
+                    OPTIONS_SELECTED_COLOR
+                } else {
+                    OPTIONS_TEXT_COLOR
+                };
+
+                parent.spawn(TextBundle::from_section(
+                    format!("{:?}: {}", option, option.value(&settings)),
+                    text_style(color),
+                ));
+            }
+        });
+}
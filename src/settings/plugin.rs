@@ -0,0 +1,52 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::schedule::InGameSet;
+
+use super::{
+    file::Settings,
+    options::{navigate_options_screen, open_options_screen, render_options_screen, OptionsScreen},
+};
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                open_options_screen.run_if(not(resource_exists::<OptionsScreen>)),
+                (navigate_options_screen, render_options_screen)
+                    .chain()
+                    .run_if(resource_exists::<OptionsScreen>),
+                apply_settings,
+            )
+                .chain()
+                .in_set(InGameSet::HandleMenu),
+        );
+    }
+}
+
+/// System to apply the [`Settings`] to the primary [`Window`] and the display resources whenever
+/// they change, including once at startup
+fn apply_settings(
+    settings: Res<Settings>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut clear_color: ResMut<ClearColor>,
+    mut ambient_light: ResMut<AmbientLight>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for mut window in windows.iter_mut() {
+        window.mode = settings.window_mode;
+        window
+            .resolution
+            .set(settings.resolution.0, settings.resolution.1);
+        window.present_mode = settings.present_mode();
+    }
+    clear_color.0 = settings.background_color;
+    ambient_light.brightness = settings.ambient_brightness;
+    ui_scale.0 = settings.ui_scale;
+}
@@ -0,0 +1,226 @@
+use std::fmt;
+
+use bevy::{app::AppExit, ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    board::{
+        bundle::{BoardLimit, GameState},
+        win_condition::{check_wincondition, GameFinished, GameResult},
+    },
+    bot::{
+        plugin::{BotRng, BotSeats},
+        strategy::{choose_legal_move, BotView, SearchBudget},
+    },
+    card::{
+        bundle::CardColor,
+        deck::DeckDefinition,
+        sequence::{CardSequence, GameSeed},
+        spawn::{get_next_card, skip_next_card, update_board_state, CardIndex},
+    },
+    cli::Cli,
+};
+
+/// Plays bot-vs-bot games back to back, one move per update, and prints the [`SimulationStats`]
+/// once all games are finished. Needs the [`HeadlessPlugin`](crate::headless::HeadlessPlugin)
+pub struct SimulationPlugin {
+    pub games: usize,
+}
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SimulationStats {
+            games: self.games,
+            ..default()
+        });
+        app.add_systems(Startup, setup_simulation);
+        app.add_systems(
+            Update,
+            (play_bot_move, check_wincondition, finish_game).chain(),
+        );
+    }
+}
+
+/// Aggregate results of all simulated games
+#[derive(Resource, Debug, Default)]
+pub struct SimulationStats {
+    /// Number of games to simulate
+    pub games: usize,
+    pub finished: usize,
+    pub draws: usize,
+    /// Cards placed over all finished games
+    pub total_moves: usize,
+    /// Colors owned by each seat, in turn order
+    pub seats: Vec<Vec<CardColor>>,
+    pub seat_wins: Vec<usize>,
+    pub color_wins: Vec<(CardColor, usize)>,
+}
+
+impl SimulationStats {
+    pub fn record(&mut self, result: GameResult, moves: usize) {
+        self.finished += 1;
+        self.total_moves += moves;
+
+        match result {
+            GameResult::Win { color } => {
+                if let Some(seat) = self.seats.iter().position(|seat| seat.contains(&color)) {
+                    self.seat_wins[seat] += 1;
+                }
+                if let Some((_, wins)) = self.color_wins.iter_mut().find(|(c, _)| *c == color) {
+                    *wins += 1;
+                }
+            }
+            GameResult::Draw => self.draws += 1,
+        }
+    }
+
+    fn percentage(&self, count: usize) -> f32 {
+        100.0 * count as f32 / self.finished.max(1) as f32
+    }
+}
+
+impl fmt::Display for SimulationStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Simulated {} games", self.finished)?;
+        writeln!(
+            f,
+            "Average game length: {:.1} cards",
+            self.total_moves as f32 / self.finished.max(1) as f32
+        )?;
+        writeln!(
+            f,
+            "Draws: {} ({:.1}%)",
+            self.draws,
+            self.percentage(self.draws)
+        )?;
+        for (seat, (colors, wins)) in self.seats.iter().zip(&self.seat_wins).enumerate() {
+            writeln!(
+                f,
+                "Seat {} {:?}: {} wins ({:.1}%)",
+                seat + 1,
+                colors,
+                wins,
+                self.percentage(*wins)
+            )?;
+        }
+        for (color, wins) in self.color_wins.iter() {
+            writeln!(
+                f,
+                "{:?}: {} wins ({:.1}%)",
+                color,
+                wins,
+                self.percentage(*wins)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Group of all system parameters used to play simulated games
+#[derive(SystemParam)]
+pub struct SimulationContext<'w> {
+    pub board_state: ResMut<'w, GameState>,
+    pub card_index: ResMut<'w, CardIndex>,
+    pub card_sequence: ResMut<'w, CardSequence>,
+    pub game_seed: ResMut<'w, GameSeed>,
+    pub deck: Res<'w, DeckDefinition>,
+    pub board_limit: Res<'w, BoardLimit>,
+    pub bot_seats: ResMut<'w, BotSeats>,
+    pub bot_rng: ResMut<'w, BotRng>,
+    pub budget: Res<'w, SearchBudget>,
+}
+
+impl SimulationContext<'_> {
+    /// Clear the board and deal a new [`CardSequence`] from the [`GameSeed`]
+    pub fn new_game(&mut self, game_seed: GameSeed) {
+        *self.board_state = GameState::reset();
+        *self.card_sequence = CardSequence::generate_full_sequence(&self.deck, game_seed);
+        *self.game_seed = game_seed;
+        self.card_index.index = 0;
+        *self.bot_rng = BotRng::from_game_seed(game_seed);
+    }
+}
+
+/// Seat a bot everywhere, the bots and engines given on the command line first
+fn setup_simulation(
+    mut commands: Commands,
+    mut stats: ResMut<SimulationStats>,
+    deck: Res<DeckDefinition>,
+    cli: Res<Cli>,
+) {
+    stats.seats = deck.players();
+    stats.seat_wins = vec![0; stats.seats.len()];
+    stats.color_wins = deck.colors.iter().map(|color| (*color, 0)).collect();
+
+    let mut bot_seats = BotSeats::all(deck.num_players(), &cli.bot_setups());
+    bot_seats.seat_engines(cli.bots.len(), &cli.engines);
+    info!(
+        "Simulating {} games with {:?}",
+        stats.games,
+        bot_seats.setups()
+    );
+    commands.insert_resource(bot_seats);
+}
+
+/// System to let the bot whose turn it is place the next
+/// [`Card`](crate::card::bundle::Card), without rendering it. The card is skipped if it fits
+/// nowhere
+pub fn play_bot_move(mut context: SimulationContext) {
+    let Some(card) = get_next_card(&context.card_index, &context.card_sequence) else {
+        return;
+    };
+
+    let index = context.card_index.index;
+    let seat = context.bot_seats.seat_for_turn(index);
+    let players = context.deck.players();
+    let view = BotView {
+        board_state: &context.board_state,
+        card,
+        board_limit: *context.board_limit,
+        seat,
+        players: &players,
+        deck: &context.deck,
+        played: &context.card_sequence.cards[..index],
+        budget: *context.budget,
+    };
+    let chosen_move = match context.bot_seats.bot_for_turn(index) {
+        Some(bot_seat) => choose_legal_move(bot_seat.bot().as_mut(), &view, &mut context.bot_rng.0),
+        None => None,
+    };
+
+    match chosen_move {
+        Some(spawn_coordinates) => update_board_state(
+            &spawn_coordinates,
+            &card,
+            &mut context.board_state,
+            &mut context.card_index,
+        ),
+        None => skip_next_card(&mut context.board_state, &mut context.card_index),
+    }
+}
+
+/// System to record finished games and start the next one, quits after the last game
+fn finish_game(
+    mut game_finished: EventReader<GameFinished>,
+    mut stats: ResMut<SimulationStats>,
+    cli: Res<Cli>,
+    mut context: SimulationContext,
+    mut exit: EventWriter<AppExit>,
+) {
+    let Some(GameFinished(result)) = game_finished.read().last() else {
+        return;
+    };
+    stats.record(*result, context.board_state.num_cards());
+
+    if stats.finished >= stats.games {
+        println!("{}", *stats);
+        exit.send(AppExit);
+        return;
+    }
+
+    // Consecutive seeds keep a seeded simulation reproducible
+    let game_seed = cli
+        .seed
+        .map(|seed| GameSeed(seed.wrapping_add(stats.finished as u64)))
+        .unwrap_or_else(GameSeed::random);
+    context.new_game(game_seed);
+}
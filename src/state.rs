@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+
+use crate::{
+    board::{
+        bundle::GameState,
+        restart::{despawn_board, ResetContext},
+        win_condition::{winning_line, GameFinished, PlayerWinEntity},
+    },
+    card::{
+        sequence::{CardSequence, GameSeed},
+        spawn::CardIndex,
+    },
+    daily::DailyChallenge,
+    menu::setup::SetupScreen,
+    replay::viewer::ReplayViewer,
+    schedule::InGameSet,
+};
+
+/// Screen the game is on, gameplay systems only run in [`AppState::InGame`]
+#[derive(States, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AppState {
+    #[default]
+    MainMenu,
+    /// Seats are being filled before a new game is dealt, the board is empty. Only shows the
+    /// [`SetupScreen`] coming from the main menu
+    PlayerSetup,
+    InGame,
+    /// The game is on hold, the board stays as it was
+    Paused,
+    /// The game is won or drawn, taking back the last move continues it
+    GameOver,
+    /// A [`ReplayViewer`] is stepping through a finished game
+    Replay,
+}
+
+impl AppState {
+    /// Is the board on screen, rather than hidden behind a menu?
+    pub fn shows_board(&self) -> bool {
+        matches!(
+            self,
+            AppState::InGame | AppState::Paused | AppState::GameOver | AppState::Replay
+        )
+    }
+}
+
+/// Present to deal the current [`GameSeed`] again on leaving the player setup, starting the round
+/// over with the same cards
+#[derive(Resource, Debug, Default)]
+pub struct Redeal;
+
+/// Run condition for systems that only make sense while the board is on screen
+pub fn board_visible(state: Res<State<AppState>>) -> bool {
+    state.shows_board()
+}
+
+/// Run condition for the shortcuts that leave the current game for another one. A game still being
+/// played is only left through the pause menu, which asks first
+pub fn can_start_game(state: Res<State<AppState>>) -> bool {
+    matches!(
+        state.get(),
+        AppState::MainMenu | AppState::GameOver | AppState::Replay
+    )
+}
+
+/// Moves between the [`AppState`]s, dealing and clearing the board on the way
+pub struct StatePlugin {
+    /// State the app starts in
+    pub initial: AppState,
+}
+
+impl Plugin for StatePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_state(self.initial);
+        app.add_systems(OnEnter(AppState::PlayerSetup), despawn_board);
+        app.add_systems(
+            Update,
+            start_game
+                .run_if(in_state(AppState::PlayerSetup))
+                .run_if(not(resource_exists::<SetupScreen>)),
+        );
+        app.add_systems(OnExit(AppState::PlayerSetup), deal_game);
+        app.add_systems(OnEnter(AppState::Replay), (despawn_board, watch_replay));
+        app.add_systems(OnExit(AppState::Replay), stop_replay);
+        app.add_systems(
+            Update,
+            (
+                finish_game.run_if(in_state(AppState::InGame)),
+                continue_game.run_if(in_state(AppState::GameOver)),
+            )
+                .in_set(InGameSet::LogState),
+        );
+    }
+}
+
+/// System to start playing once the seats are filled, right away when restarting with the same
+/// seats
+fn start_game(mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(AppState::InGame);
+}
+
+/// System to deal a new game on leaving the player setup. The same cards again on a [`Redeal`],
+/// today's decks during the [`DailyChallenge`] and a random [`GameSeed`] otherwise
+fn deal_game(
+    mut commands: Commands,
+    redeal: Option<Res<Redeal>>,
+    challenge: Option<Res<DailyChallenge>>,
+    mut context: ResetContext,
+) {
+    let game_seed = if redeal.is_some() {
+        commands.remove_resource::<Redeal>();
+        *context.game_seed
+    } else {
+        challenge
+            .map(|challenge| challenge.seed())
+            .unwrap_or_else(GameSeed::random)
+    };
+    info!("Dealing a new game with {:?}", game_seed);
+    context.new_game(game_seed);
+}
+
+/// System to set the board up for the [`ReplayViewer`] that was just inserted
+fn watch_replay(viewer: Res<ReplayViewer>, mut context: ResetContext) {
+    context.reset(
+        GameSeed(viewer.replay.game.seed),
+        CardSequence {
+            cards: viewer.replay.game.deck.clone(),
+        },
+    );
+}
+
+/// System to put the [`ReplayViewer`] away, along with the win notification it may have shown
+fn stop_replay(mut commands: Commands, mut player_win_entity: ResMut<PlayerWinEntity>) {
+    commands.remove_resource::<ReplayViewer>();
+    if let Some(entity) = player_win_entity.0.take() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// System to end the game once it is won or drawn
+fn finish_game(
+    mut game_finished: EventReader<GameFinished>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if game_finished.read().last().is_some() {
+        next_state.set(AppState::GameOver);
+    }
+}
+
+/// System to go on playing once the finishing move is taken back
+fn continue_game(
+    board_state: Res<GameState>,
+    card_index: Res<CardIndex>,
+    card_sequence: Res<CardSequence>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if !board_state.is_changed() {
+        return;
+    }
+    let finished =
+        winning_line(&board_state).is_some() || card_index.index >= card_sequence.cards.len();
+    if !finished {
+        next_state.set(AppState::InGame);
+    }
+}
@@ -0,0 +1,320 @@
+use std::fmt;
+
+use bevy::{app::AppExit, prelude::*};
+
+use crate::{
+    board::win_condition::{check_wincondition, GameFinished, GameResult},
+    bot::plugin::BotSeats,
+    card::{deck::DeckDefinition, sequence::GameSeed},
+    simulation::{play_bot_move, SimulationContext},
+};
+
+/// z-score of a two sided 95% confidence interval
+const CONFIDENCE_Z: f64 = 1.96;
+
+/// Plays bot A against bot B with swapped seats: every seed is played twice, A moves first in
+/// the first game and B in the second, so neither profits from a lucky deal. Needs the
+/// [`HeadlessPlugin`](crate::headless::HeadlessPlugin) and [`BotSeats`] with A in the first and
+/// B in the second seat
+pub struct TournamentPlugin {
+    pub tournament: Tournament,
+}
+
+impl Plugin for TournamentPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.tournament.clone());
+        app.init_resource::<TournamentStats>();
+        app.add_systems(
+            Update,
+            (play_bot_move, check_wincondition, finish_tournament_game).chain(),
+        );
+    }
+}
+
+/// Settings of a tournament
+#[derive(Resource, Debug, Clone)]
+pub struct Tournament {
+    /// Seed of the first pair of games, the next pairs count up from it
+    pub first_seed: u64,
+    /// Stop after this many games, even if the [`Sprt`] did not decide yet. Rounded up to whole
+    /// pairs
+    pub max_games: usize,
+    pub sprt: Option<Sprt>,
+}
+
+/// Sequential probability ratio test, stops the tournament as soon as the games played make clear
+/// which of two Elo differences is more likely
+#[derive(Debug, Clone, Copy)]
+pub struct Sprt {
+    /// Elo difference of A over B if A is not stronger
+    pub elo0: f64,
+    /// Elo difference of A over B if A is stronger
+    pub elo1: f64,
+    /// Chance to accept `elo1` while `elo0` is true
+    pub alpha: f64,
+    /// Chance to accept `elo0` while `elo1` is true
+    pub beta: f64,
+}
+
+impl Sprt {
+    /// Log likelihood ratio below which `elo0` is accepted
+    pub fn lower_bound(&self) -> f64 {
+        (self.beta / (1.0 - self.alpha)).ln()
+    }
+
+    /// Log likelihood ratio above which `elo1` is accepted
+    pub fn upper_bound(&self) -> f64 {
+        ((1.0 - self.beta) / self.alpha).ln()
+    }
+}
+
+/// Outcome of a [`Sprt`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SprtResult {
+    AcceptElo0,
+    AcceptElo1,
+    Continue,
+}
+
+/// Results of all games, from the point of view of bot A
+#[derive(Resource, Debug, Default)]
+pub struct TournamentStats {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl TournamentStats {
+    pub fn games(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Average points of A per game, a win is 1 and a draw is 0.5
+    pub fn score(&self) -> f64 {
+        (self.wins as f64 + self.draws as f64 / 2.0) / self.games().max(1) as f64
+    }
+
+    /// Variance of the points of A in a single game
+    fn variance(&self) -> f64 {
+        let score = self.score();
+        let games = self.games().max(1) as f64;
+        (self.wins as f64 * (1.0 - score).powi(2)
+            + self.draws as f64 * (0.5 - score).powi(2)
+            + self.losses as f64 * score.powi(2))
+            / games
+    }
+
+    /// Whether A won or lost every game, its Elo difference is only bounded then
+    pub fn is_perfect(&self) -> bool {
+        self.games() > 0 && (self.wins == self.games() || self.losses == self.games())
+    }
+
+    /// Keep a score half a game away from 0 and 1, where the Elo difference is infinite
+    fn bounded_score(&self, score: f64) -> f64 {
+        let margin = 0.5 / self.games().max(1) as f64;
+        score.clamp(margin, 1.0 - margin)
+    }
+
+    /// Elo difference of A over B
+    pub fn elo(&self) -> f64 {
+        score_to_elo(self.bounded_score(self.score()))
+    }
+
+    /// 95% confidence interval of [`TournamentStats::elo`]
+    pub fn elo_interval(&self) -> (f64, f64) {
+        let margin = CONFIDENCE_Z * (self.variance() / self.games().max(1) as f64).sqrt();
+        let score = self.score();
+        (
+            score_to_elo(self.bounded_score(score - margin)),
+            score_to_elo(self.bounded_score(score + margin)),
+        )
+    }
+
+    /// Log likelihood ratio of `elo1` over `elo0`, approximating the trinomial distribution of
+    /// wins, draws and losses with a normal distribution
+    pub fn llr(&self, sprt: &Sprt) -> f64 {
+        let variance = self.variance();
+        if variance <= 0.0 {
+            return 0.0;
+        }
+        let score0 = elo_to_score(sprt.elo0);
+        let score1 = elo_to_score(sprt.elo1);
+        self.games() as f64 * (score1 - score0) * (2.0 * self.score() - score0 - score1)
+            / (2.0 * variance)
+    }
+
+    pub fn sprt(&self, sprt: &Sprt) -> SprtResult {
+        let llr = self.llr(sprt);
+        if llr <= sprt.lower_bound() {
+            SprtResult::AcceptElo0
+        } else if llr >= sprt.upper_bound() {
+            SprtResult::AcceptElo1
+        } else {
+            SprtResult::Continue
+        }
+    }
+}
+
+impl fmt::Display for TournamentStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (low, high) = self.elo_interval();
+        writeln!(
+            f,
+            "Games: {} (A: {} wins, {} draws, {} losses)",
+            self.games(),
+            self.wins,
+            self.draws,
+            self.losses
+        )?;
+        writeln!(f, "Score of A: {:.1}%", 100.0 * self.score())?;
+        write!(
+            f,
+            "Elo difference A - B: {:+.1} (95% interval {:+.1} to {:+.1})",
+            self.elo(),
+            low,
+            high
+        )?;
+        if self.is_perfect() {
+            write!(
+                f,
+                "\nA won or lost every game, the Elo is estimated as if it had dropped half a game"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Expected score of the stronger player for an Elo difference
+pub fn elo_to_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+/// Elo difference for an average score, infinite for a score of 0 or 1
+pub fn score_to_elo(score: f64) -> f64 {
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+/// Seat of bot A in a game, A and B swap seats every game
+fn seat_of_a(game: usize) -> usize {
+    game % 2
+}
+
+/// System to score a finished game for bot A, and start the next game with swapped seats or quit
+/// once the tournament is decided
+fn finish_tournament_game(
+    mut game_finished: EventReader<GameFinished>,
+    tournament: Res<Tournament>,
+    mut stats: ResMut<TournamentStats>,
+    deck: Res<DeckDefinition>,
+    mut context: SimulationContext,
+    mut exit: EventWriter<AppExit>,
+) {
+    let Some(GameFinished(result)) = game_finished.read().last() else {
+        return;
+    };
+
+    let colors_of_a = &deck.players()[seat_of_a(stats.games())];
+    match result {
+        GameResult::Win { color } if colors_of_a.contains(color) => stats.wins += 1,
+        GameResult::Win { .. } => stats.losses += 1,
+        GameResult::Draw => stats.draws += 1,
+    }
+
+    // Only whole pairs are judged, so neither bot is scored on a deal only it moved first in
+    let pair_finished = stats.games().is_multiple_of(2);
+    let decided = tournament
+        .sprt
+        .is_some_and(|sprt| stats.sprt(&sprt) != SprtResult::Continue);
+    if pair_finished && (decided || stats.games() >= tournament.max_games) {
+        println!("{}", *stats);
+        if let Some(sprt) = tournament.sprt {
+            println!(
+                "SPRT elo0 {} elo1 {}: LLR {:.2} ({:.2} to {:.2}), {:?}",
+                sprt.elo0,
+                sprt.elo1,
+                stats.llr(&sprt),
+                sprt.lower_bound(),
+                sprt.upper_bound(),
+                stats.sprt(&sprt)
+            );
+        }
+        exit.send(AppExit);
+        return;
+    }
+
+    // Both games of a pair are dealt from the same seed
+    let game = stats.games();
+    let game_seed = GameSeed(tournament.first_seed.wrapping_add(game as u64 / 2));
+    swap_seats(&mut context.bot_seats);
+    context.new_game(game_seed);
+}
+
+fn swap_seats(bot_seats: &mut BotSeats) {
+    if bot_seats.0.len() >= 2 {
+        bot_seats.0.swap(0, 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPRT: Sprt = Sprt {
+        elo0: 0.0,
+        elo1: 10.0,
+        alpha: 0.05,
+        beta: 0.05,
+    };
+
+    fn stats(wins: usize, draws: usize, losses: usize) -> TournamentStats {
+        TournamentStats {
+            wins,
+            draws,
+            losses,
+        }
+    }
+
+    #[test]
+    fn elo_and_score_convert_both_ways() {
+        assert_eq!(elo_to_score(0.0), 0.5);
+        assert_eq!(score_to_elo(0.5), 0.0);
+        for elo in [-400.0, -100.0, 35.0, 200.0, 800.0] {
+            assert!((score_to_elo(elo_to_score(elo)) - elo).abs() < 1e-9);
+        }
+        assert!((elo_to_score(400.0) - 10.0 / 11.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn sprt_bounds_follow_alpha_and_beta() {
+        assert!((SPRT.lower_bound() - (0.05f64 / 0.95).ln()).abs() < 1e-12);
+        assert!((SPRT.upper_bound() - (0.95f64 / 0.05).ln()).abs() < 1e-12);
+        assert!(SPRT.lower_bound() < 0.0 && SPRT.upper_bound() > 0.0);
+    }
+
+    #[test]
+    fn llr_favours_the_likelier_elo() {
+        assert_eq!(stats(0, 0, 0).llr(&SPRT), 0.0);
+        assert_eq!(stats(0, 10, 0).llr(&SPRT), 0.0);
+        assert!(stats(60, 10, 30).llr(&SPRT) > 0.0);
+        assert!(stats(30, 10, 60).llr(&SPRT) < 0.0);
+        assert!(stats(600, 100, 300).llr(&SPRT) > stats(60, 10, 30).llr(&SPRT));
+    }
+
+    #[test]
+    fn sprt_decides_clear_results() {
+        assert_eq!(stats(5, 0, 5).sprt(&SPRT), SprtResult::Continue);
+        assert_eq!(stats(600, 100, 300).sprt(&SPRT), SprtResult::AcceptElo1);
+        assert_eq!(stats(300, 100, 600).sprt(&SPRT), SprtResult::AcceptElo0);
+    }
+
+    #[test]
+    fn perfect_scores_keep_a_finite_elo() {
+        for stats in [stats(10, 0, 0), stats(0, 0, 10)] {
+            let (low, high) = stats.elo_interval();
+            assert!(stats.is_perfect());
+            assert!(stats.elo().is_finite() && low.is_finite() && high.is_finite());
+            assert!(low <= stats.elo() && stats.elo() <= high);
+        }
+        assert!(!stats(9, 1, 0).is_perfect());
+    }
+}
@@ -0,0 +1,388 @@
+use std::{error::Error, fs, path::Path};
+
+use bevy::prelude::*;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// File the running [`Tourney`] is persisted to after every game, relative to the working
+/// directory
+pub const TOURNEY_FILE: &str = "tourney.ron";
+
+/// How the matches of a [`Tourney`] are paired
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+pub enum TourneyFormat {
+    /// Everyone plays everyone once, the most match wins takes the tourney
+    #[default]
+    RoundRobin,
+    /// Losers are out, winners meet in the next round until one is left
+    SingleElimination,
+}
+
+/// Two registered players playing games until one has [`Tourney::points_to_win`] points, a won
+/// game is worth a point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Match {
+    /// Indices into [`Tourney::players`]
+    pub players: [usize; 2],
+    pub points: [u32; 2],
+    pub draws: u32,
+}
+
+impl Match {
+    pub fn new(first: usize, second: usize) -> Self {
+        Self {
+            players: [first, second],
+            points: [0, 0],
+            draws: 0,
+        }
+    }
+
+    pub fn games(&self) -> u32 {
+        self.points[0] + self.points[1] + self.draws
+    }
+
+    /// Players in seat order for the next game, they take turns moving first
+    pub fn seated_players(&self) -> [usize; 2] {
+        let [first, second] = self.players;
+        if self.games().is_multiple_of(2) {
+            [first, second]
+        } else {
+            [second, first]
+        }
+    }
+
+    /// The player that reached `points_to_win`, if any
+    pub fn winner(&self, points_to_win: u32) -> Option<usize> {
+        self.players
+            .iter()
+            .zip(self.points)
+            .find(|(_, points)| *points >= points_to_win)
+            .map(|(player, _)| *player)
+    }
+
+    pub fn loser(&self, points_to_win: u32) -> Option<usize> {
+        let winner = self.winner(points_to_win)?;
+        self.players.into_iter().find(|player| *player != winner)
+    }
+}
+
+/// Matches played at the same time, players with a bye sit this round out
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Round {
+    pub matches: Vec<Match>,
+    pub byes: Vec<usize>,
+}
+
+/// Results of a single player over all finished matches
+#[derive(Debug, Clone, Default)]
+pub struct Standing {
+    /// Index into [`Tourney::players`]
+    pub player: usize,
+    pub played: u32,
+    pub won: u32,
+    pub lost: u32,
+    /// Games won over all matches, breaks ties between equal match wins
+    pub points: u32,
+}
+
+/// A tourney between local players, present while one is running
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct Tourney {
+    pub players: Vec<String>,
+    pub format: TourneyFormat,
+    pub points_to_win: u32,
+    pub rounds: Vec<Round>,
+}
+
+impl Tourney {
+    pub fn new(
+        players: Vec<String>,
+        format: TourneyFormat,
+        points_to_win: u32,
+    ) -> Result<Self, String> {
+        if players.len() < 2 {
+            return Err(format!(
+                "a tourney needs at least 2 players, got {}",
+                players.len()
+            ));
+        }
+        if points_to_win == 0 {
+            return Err("matches need at least 1 point to win".to_string());
+        }
+
+        let rounds = match format {
+            TourneyFormat::RoundRobin => round_robin(players.len()),
+            TourneyFormat::SingleElimination => {
+                vec![elimination_round((0..players.len()).collect())]
+            }
+        };
+        Ok(Self {
+            players,
+            format,
+            points_to_win,
+            rounds,
+        })
+    }
+
+    /// Round and match index of the first match without a winner, [`None`] once the tourney is
+    /// over
+    pub fn current_match(&self) -> Option<(usize, usize)> {
+        self.rounds.iter().enumerate().find_map(|(round, matches)| {
+            matches
+                .matches
+                .iter()
+                .position(|current| current.winner(self.points_to_win).is_none())
+                .map(|index| (round, index))
+        })
+    }
+
+    pub fn current(&self) -> Option<&Match> {
+        let (round, index) = self.current_match()?;
+        Some(&self.rounds[round].matches[index])
+    }
+
+    /// Score a finished game of the current match, `winner` is an index into
+    /// [`Tourney::players`] or [`None`] for a draw. Pairs the next elimination round once every
+    /// match of this round is decided
+    pub fn record_game(&mut self, winner: Option<usize>) {
+        let Some((round, index)) = self.current_match() else {
+            return;
+        };
+        let current = &mut self.rounds[round].matches[index];
+        match winner.and_then(|winner| current.players.iter().position(|p| *p == winner)) {
+            Some(seat) => current.points[seat] += 1,
+            None => current.draws += 1,
+        }
+
+        if self.format == TourneyFormat::SingleElimination && self.current_match().is_none() {
+            let survivors = self.survivors();
+            if survivors.len() > 1 {
+                self.rounds.push(elimination_round(survivors));
+            }
+        }
+    }
+
+    /// Players with a bye and winners of the last round, in bracket order
+    fn survivors(&self) -> Vec<usize> {
+        let Some(round) = self.rounds.last() else {
+            return Vec::new();
+        };
+        round
+            .byes
+            .iter()
+            .copied()
+            .chain(
+                round
+                    .matches
+                    .iter()
+                    .filter_map(|current| current.winner(self.points_to_win)),
+            )
+            .collect()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current_match().is_none()
+    }
+
+    /// The winner of a finished tourney: the last one standing, or the top of the standings
+    pub fn champion(&self) -> Option<usize> {
+        if !self.is_finished() {
+            return None;
+        }
+        match self.format {
+            TourneyFormat::RoundRobin => self.standings().first().map(|standing| standing.player),
+            TourneyFormat::SingleElimination => self.survivors().first().copied(),
+        }
+    }
+
+    /// Every player ordered by match wins, then by game points. In a single elimination tourney
+    /// whoever is still in comes first
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = (0..self.players.len())
+            .map(|player| Standing {
+                player,
+                ..default()
+            })
+            .collect();
+
+        for current in self.rounds.iter().flat_map(|round| round.matches.iter()) {
+            for (player, points) in current.players.iter().zip(current.points) {
+                standings[*player].points += points;
+            }
+            if let (Some(winner), Some(loser)) = (
+                current.winner(self.points_to_win),
+                current.loser(self.points_to_win),
+            ) {
+                standings[winner].played += 1;
+                standings[winner].won += 1;
+                standings[loser].played += 1;
+                standings[loser].lost += 1;
+            }
+        }
+
+        standings.sort_by(|a, b| {
+            let by_wins = b
+                .won
+                .cmp(&a.won)
+                .then(b.points.cmp(&a.points))
+                .then(a.lost.cmp(&b.lost));
+            match self.format {
+                TourneyFormat::RoundRobin => by_wins,
+                TourneyFormat::SingleElimination => a.lost.cmp(&b.lost).then(by_wins),
+            }
+        });
+        standings
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+        Ok(ron::from_str(&contents)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Write the tourney to the [`TOURNEY_FILE`], logging failures
+    pub fn save_to_file(&self) {
+        let path = Path::new(TOURNEY_FILE);
+        if let Err(err) = self.save(path) {
+            error!("Failed to save tourney to {:?}: {}", path, err);
+        }
+    }
+}
+
+/// Pair every player with every other player once, with the circle method: one player stays put
+/// while the others rotate around it. With an odd number of players a missing player is added,
+/// whoever is paired with it has a bye
+fn round_robin(num_players: usize) -> Vec<Round> {
+    let mut circle: Vec<Option<usize>> = (0..num_players).map(Some).collect();
+    if num_players % 2 == 1 {
+        circle.push(None);
+    }
+    let size = circle.len();
+
+    (0..size - 1)
+        .map(|_| {
+            let mut round = Round::default();
+            for index in 0..size / 2 {
+                match (circle[index], circle[size - 1 - index]) {
+                    (Some(first), Some(second)) => round.matches.push(Match::new(first, second)),
+                    (Some(player), None) | (None, Some(player)) => round.byes.push(player),
+                    (None, None) => {}
+                }
+            }
+            circle[1..].rotate_right(1);
+            round
+        })
+        .collect()
+}
+
+/// Pair the best remaining seed with the worst. If the number of players is not a power of two,
+/// the best seeds get a bye so every later round is complete
+fn elimination_round(players: Vec<usize>) -> Round {
+    let num_byes = players.len().next_power_of_two() - players.len();
+    let (byes, playing) = players.split_at(num_byes);
+    let half = playing.len() / 2;
+    let matches = (0..half)
+        .map(|index| Match::new(playing[index], playing[playing.len() - 1 - index]))
+        .collect();
+    Round {
+        matches,
+        byes: byes.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every pair of players in the rounds, smallest player first
+    fn pairs(rounds: &[Round]) -> Vec<[usize; 2]> {
+        let mut pairs: Vec<_> = rounds
+            .iter()
+            .flat_map(|round| round.matches.iter())
+            .map(|current| {
+                let [first, second] = current.players;
+                [first.min(second), first.max(second)]
+            })
+            .collect();
+        pairs.sort();
+        pairs
+    }
+
+    #[test]
+    fn round_robin_pairs_everyone_once() {
+        for num_players in 2..=9 {
+            let rounds = round_robin(num_players);
+            let expected: Vec<_> = (0..num_players)
+                .flat_map(|first| (first + 1..num_players).map(move |second| [first, second]))
+                .collect();
+            assert_eq!(pairs(&rounds), expected, "{} players", num_players);
+
+            for round in rounds.iter() {
+                let mut seated: Vec<_> = round
+                    .matches
+                    .iter()
+                    .flat_map(|current| current.players)
+                    .chain(round.byes.iter().copied())
+                    .collect();
+                seated.sort();
+                assert_eq!(seated, (0..num_players).collect::<Vec<_>>());
+                assert_eq!(round.byes.len(), num_players % 2);
+            }
+        }
+    }
+
+    #[test]
+    fn elimination_round_gives_the_best_seeds_a_bye() {
+        let round = elimination_round((0..5).collect());
+        assert_eq!(round.byes, vec![0, 1, 2]);
+        assert_eq!(round.matches.len(), 1);
+        assert_eq!(round.matches[0].players, [3, 4]);
+
+        let round = elimination_round((0..4).collect());
+        assert!(round.byes.is_empty());
+        let matches: Vec<_> = round
+            .matches
+            .iter()
+            .map(|current| current.players)
+            .collect();
+        assert_eq!(matches, vec![[0, 3], [1, 2]]);
+    }
+
+    #[test]
+    fn elimination_plays_down_to_a_champion() {
+        let players = (0..6).map(|player| player.to_string()).collect();
+        let mut tourney = Tourney::new(players, TourneyFormat::SingleElimination, 1).unwrap();
+        assert_eq!(tourney.rounds[0].byes, vec![0, 1]);
+
+        // The lower index wins every match
+        while let Some(current) = tourney.current() {
+            let winner = current.players[0].min(current.players[1]);
+            tourney.record_game(Some(winner));
+        }
+
+        assert_eq!(tourney.rounds.len(), 3);
+        assert_eq!(tourney.champion(), Some(0));
+        let survivors: Vec<_> = tourney.rounds[1]
+            .matches
+            .iter()
+            .flat_map(|current| current.players)
+            .collect();
+        assert_eq!(survivors.len(), 4);
+    }
+
+    #[test]
+    fn draws_do_not_decide_a_match() {
+        let players = vec!["a".to_string(), "b".to_string()];
+        let mut tourney = Tourney::new(players, TourneyFormat::RoundRobin, 2).unwrap();
+        tourney.record_game(None);
+        tourney.record_game(Some(1));
+        assert!(!tourney.is_finished());
+        tourney.record_game(Some(1));
+        assert_eq!(tourney.champion(), Some(1));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod bracket;
+pub mod plugin;
+pub mod standings;
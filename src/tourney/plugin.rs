@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+
+use crate::{
+    board::{
+        restart::DealNumber,
+        win_condition::{GameFinished, GameResult},
+    },
+    card::deck::DeckDefinition,
+    daily::DailyChallenge,
+    replay::viewer::ReplayViewer,
+    schedule::InGameSet,
+};
+
+use super::{
+    bracket::Tourney,
+    standings::{close_standings, render_standings, StandingsScreen},
+};
+
+pub struct TourneyPlugin;
+
+impl Plugin for TourneyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Startup,
+            show_standings_on_resume.run_if(resource_exists::<Tourney>),
+        );
+        app.add_systems(
+            Update,
+            record_tourney_game
+                .run_if(resource_exists::<Tourney>)
+                .run_if(not(resource_exists::<DailyChallenge>))
+                .run_if(not(resource_exists::<ReplayViewer>))
+                .in_set(InGameSet::LogState),
+        );
+        app.add_systems(
+            Update,
+            (
+                close_standings.run_if(resource_exists::<StandingsScreen>),
+                render_standings.run_if(resource_added::<StandingsScreen>),
+            )
+                .chain()
+                .in_set(InGameSet::HandleMenu),
+        );
+    }
+}
+
+/// System to show where a resumed tourney left off
+fn show_standings_on_resume(mut commands: Commands, tourney: Res<Tourney>) {
+    let games: u32 = tourney
+        .rounds
+        .iter()
+        .flat_map(|round| round.matches.iter())
+        .map(|current| current.games())
+        .sum();
+    if games > 0 {
+        commands.insert_resource(StandingsScreen);
+    }
+}
+
+/// System to score a finished game for the player holding the winning color, save the
+/// [`Tourney`] and show the standings. A game finished again after an undo is only scored once
+fn record_tourney_game(
+    mut commands: Commands,
+    mut game_finished: EventReader<GameFinished>,
+    mut tourney: ResMut<Tourney>,
+    deck: Res<DeckDefinition>,
+    deal_number: Res<DealNumber>,
+    mut recorded: Local<Option<DealNumber>>,
+) {
+    for GameFinished(result) in game_finished.read() {
+        if *recorded == Some(*deal_number) {
+            continue;
+        }
+        let Some(current) = tourney.current() else {
+            continue;
+        };
+        *recorded = Some(*deal_number);
+
+        let seated_players = current.seated_players();
+        let winner = match result {
+            GameResult::Win { color } => deck
+                .players()
+                .iter()
+                .position(|colors| colors.contains(color))
+                .and_then(|seat| seated_players.get(seat).copied()),
+            GameResult::Draw => None,
+        };
+        match winner {
+            Some(winner) => info!("Tourney game won by {}", tourney.players[winner]),
+            None => info!("Tourney game ended in a draw"),
+        }
+
+        tourney.record_game(winner);
+        tourney.save_to_file();
+        commands.insert_resource(StandingsScreen);
+    }
+}
@@ -0,0 +1,174 @@
+use bevy::prelude::*;
+
+use crate::{
+    asset_loader::AssetStore,
+    card::deck::DeckDefinition,
+    keys::keymap::{Action, ActionInput},
+    state::AppState,
+};
+
+use super::bracket::Tourney;
+
+const STANDINGS_FONT_SIZE: f32 = 30.0;
+const STANDINGS_PADDING: f32 = 40.0;
+const STANDINGS_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.85);
+const STANDINGS_TEXT_COLOR: Color = Color::WHITE;
+const STANDINGS_HEADER_COLOR: Color = Color::GOLD;
+const NAME_COLUMN_WIDTH: f32 = 300.0;
+const NUMBER_COLUMN_WIDTH: f32 = 120.0;
+
+/// Present while the standings are shown between two tourney games
+#[derive(Resource)]
+pub struct StandingsScreen;
+
+/// Marker component for the root node of the standings screen
+#[derive(Component)]
+pub struct StandingsScreenMarker;
+
+/// System to show the standings table, the current match and how to continue
+pub fn render_standings(
+    mut commands: Commands,
+    tourney: Res<Tourney>,
+    asset_store: Res<AssetStore>,
+    state: Res<State<AppState>>,
+) {
+    let text_style = |color: Color| TextStyle {
+        font: asset_store.font.clone(),
+        font_size: STANDINGS_FONT_SIZE,
+        color,
+    };
+    let cell = |width: f32| NodeBundle {
+        style: Style {
+            width: Val::Px(width),
+            ..default()
+        },
+        ..default()
+    };
+
+    let title = match tourney.champion() {
+        Some(champion) => format!("{} wins the tourney!", tourney.players[champion]),
+        None => "Standings".to_string(),
+    };
+    // After a game the standings cover the game over screen, its rematch deals the next game
+    let play_on = if *state.get() == AppState::GameOver {
+        "Restart to close, then Rematch to"
+    } else {
+        "Restart to"
+    };
+    let next = match tourney.current() {
+        Some(current) => format!(
+            "Next: {} vs {}, {} - {}. {} play on",
+            tourney.players[current.players[0]],
+            tourney.players[current.players[1]],
+            current.points[0],
+            current.points[1],
+            play_on
+        ),
+        None => format!("{} play a free game", play_on),
+    };
+
+    let header = ["Player", "Played", "Won", "Lost", "Points"].map(str::to_string);
+    let rows = tourney.standings().into_iter().map(|standing| {
+        [
+            tourney.players[standing.player].clone(),
+            standing.played.to_string(),
+            standing.won.to_string(),
+            standing.lost.to_string(),
+            standing.points.to_string(),
+        ]
+    });
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(STANDINGS_PADDING)),
+                    ..default()
+                },
+                background_color: STANDINGS_BACKGROUND_COLOR.into(),
+                z_index: ZIndex::Global(i32::MAX),
+                ..default()
+            },
+            StandingsScreenMarker,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                title,
+                text_style(STANDINGS_HEADER_COLOR),
+            ));
+
+            for (index, row) in std::iter::once(header).chain(rows).enumerate() {
+                let color = if index == 0 {
+                    STANDINGS_HEADER_COLOR
+                } else {
+                    STANDINGS_TEXT_COLOR
+                };
+                parent.spawn(NodeBundle::default()).with_children(|parent| {
+                    for (column, text) in row.into_iter().enumerate() {
+                        let width = if column == 0 {
+                            NAME_COLUMN_WIDTH
+                        } else {
+                            NUMBER_COLUMN_WIDTH
+                        };
+                        parent.spawn(cell(width)).with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(text, text_style(color)));
+                        });
+                    }
+                });
+            }
+
+            parent.spawn(TextBundle::from_section(
+                next,
+                text_style(STANDINGS_TEXT_COLOR),
+            ));
+        });
+}
+
+/// System to close the standings as soon as the next game is started
+pub fn close_standings(
+    mut commands: Commands,
+    input: ActionInput,
+    query: Query<Entity, With<StandingsScreenMarker>>,
+) {
+    if input.just_pressed(Action::Restart) {
+        for entity in query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        commands.remove_resource::<StandingsScreen>();
+    }
+}
+
+/// Who plays which colors in the current match and the match score, shown on the
+/// [`Hud`](crate::hud::Hud)
+pub fn match_score(tourney: &Tourney, deck: &DeckDefinition) -> String {
+    match (tourney.current_match(), tourney.current()) {
+        (Some((round, _)), Some(current)) => {
+            let seats = deck.players();
+            let [first, second] = current.seated_players();
+            let player = |player: usize, seat: usize| {
+                let colors: String = seats[seat].iter().map(|color| color.letter()).collect();
+                let index = current
+                    .players
+                    .iter()
+                    .position(|p| *p == player)
+                    .unwrap_or(0);
+                format!(
+                    "{} ({}) {}",
+                    tourney.players[player], colors, current.points[index]
+                )
+            };
+            format!(
+                "Round {}: {} - {}, first to {}",
+                round + 1,
+                player(first, 0),
+                player(second, 1),
+                tourney.points_to_win
+            )
+        }
+        _ => "Tourney finished".to_string(),
+    }
+}